@@ -0,0 +1,77 @@
+//! Compare, with more and more threads, the searches on a regex shared
+//! by all threads and on the per-thread clones given with the `T` flag.
+//!
+//! Run with `cargo bench --bench contention`. The difference only shows
+//! on a machine with several cores.
+
+use {
+    lazy_regex::*,
+    std::{
+        hint::black_box,
+        thread,
+        time::{
+            Duration,
+            Instant,
+        },
+    },
+};
+
+const SEARCHES_PER_THREAD: usize = 200_000;
+
+static LINES: &[&str] = &[
+    "GET /index.html HTTP/1.1",
+    "POST /api/v2/items?id=1234 HTTP/1.1",
+    "DELETE /api/v2/items/98765 HTTP/2",
+    "HEAD / HTTP/1.0",
+];
+
+fn shared(line: &str) -> Option<&str> {
+    regex_captures!(r"^(\w+) (/\S*) HTTP/([\d.]+)$", line).map(|(_, _, path, _)| path)
+}
+
+fn thread_local(line: &str) -> Option<&str> {
+    regex_captures!(r"^(\w+) (/\S*) HTTP/([\d.]+)$"T, line).map(|(_, _, path, _)| path)
+}
+
+/// Run the searches on all threads at once, and return the time
+/// taken by the slowest thread
+fn run(threads: usize, search: fn(&str) -> Option<&str>) -> Duration {
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            thread::spawn(move || {
+                let start = Instant::now();
+                for i in 0..SEARCHES_PER_THREAD {
+                    black_box(search(black_box(LINES[i % LINES.len()])));
+                }
+                start.elapsed()
+            })
+        })
+        .collect();
+    handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .max()
+        .unwrap_or_default()
+}
+
+fn main() {
+    let cores = thread::available_parallelism().map_or(1, |n| n.get());
+    println!("{cores} cores, {SEARCHES_PER_THREAD} searches per thread");
+    println!("{:>8} {:>14} {:>14}", "threads", "shared", "thread local");
+    // the first run builds the regexes
+    run(1, shared);
+    run(1, thread_local);
+    let mut threads = 1;
+    while threads <= cores * 2 {
+        let shared = run(threads, shared);
+        let local = run(threads, thread_local);
+        let per_search = |d: Duration| d.as_nanos() as usize / SEARCHES_PER_THREAD;
+        println!(
+            "{:>8} {:>11} ns {:>11} ns",
+            threads,
+            per_search(shared),
+            per_search(local),
+        );
+        threads *= 2;
+    }
+}
@@ -0,0 +1,79 @@
+#![no_std]
+
+extern crate alloc;
+
+use {
+    alloc::borrow::Cow,
+    core::{
+        alloc::{
+            GlobalAlloc,
+            Layout,
+        },
+        cell::UnsafeCell,
+        ptr,
+        sync::atomic::{
+            AtomicUsize,
+            Ordering,
+        },
+    },
+    lazy_regex::*,
+};
+
+const ARENA_SIZE: usize = 256 * 1024;
+
+/// A bump allocator over a static arena: memory is never freed,
+/// which is enough for a few regexes built once
+struct Arena {
+    memory: UnsafeCell<[u8; ARENA_SIZE]>,
+    next: AtomicUsize,
+}
+
+unsafe impl Sync for Arena {}
+
+unsafe impl GlobalAlloc for Arena {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let base = self.memory.get() as usize;
+        let mut next = self.next.load(Ordering::Relaxed);
+        loop {
+            let start = (base + next).next_multiple_of(layout.align()) - base;
+            let end = start + layout.size();
+            if end > ARENA_SIZE {
+                return ptr::null_mut();
+            }
+            match self.next.compare_exchange_weak(next, end, Ordering::Relaxed, Ordering::Relaxed) {
+                Ok(_) => return (base + start) as *mut u8,
+                Err(current) => next = current,
+            }
+        }
+    }
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {}
+}
+
+#[global_allocator]
+static ALLOCATOR: Arena = Arena {
+    memory: UnsafeCell::new([0; ARENA_SIZE]),
+    next: AtomicUsize::new(0),
+};
+
+#[panic_handler]
+fn panic(_: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
+
+pub static SECTION: Lazy<Regex> = lazy_regex!(r"^\[(\w+)\]$");
+
+/// Parse a `key = value` line of a config blob
+pub fn key_value(line: &str) -> Option<(&str, &str)> {
+    let (_, key, value) = regex_captures!(r"^\s*(\w+)\s*=\s*(.*?)\s*$", line)?;
+    Some((key, value))
+}
+
+/// Return the name of the section, if the line starts one
+pub fn section(line: &str) -> Option<&str> {
+    SECTION.captures(line)?.get(1).map(|m| m.as_str())
+}
+
+/// Remove the comment at the end of the line, if any
+pub fn uncommented(line: &str) -> Cow<'_, str> {
+    regex_remove!(r"\s*#.*$", line)
+}
@@ -1,3 +1,6 @@
+// the examples compare with the expected booleans, for readability
+#![allow(clippy::bool_assert_comparison)]
+
 use lazy_regex::*;
 
 pub static SHARED: Lazy<Regex> = lazy_regex!("^test$");
@@ -0,0 +1,851 @@
+// we return the errors of regex-automata as they are, whatever their size
+#![allow(clippy::result_large_err)]
+
+use {
+    regex_automata::{
+        hybrid::dfa::{
+            self as hybrid,
+            OverlappingState,
+        },
+        meta::{
+            self,
+            BuildError,
+        },
+        nfa::thompson,
+        util::{
+            captures,
+            iter::Searcher,
+            pool::Pool,
+            syntax,
+        },
+        Anchored,
+        Input,
+        MatchKind,
+    },
+    std::{
+        borrow::Cow,
+        fmt,
+        marker::PhantomData,
+        ops::Range,
+        panic::{
+            RefUnwindSafe,
+            UnwindSafe,
+        },
+        sync::OnceLock,
+    },
+};
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for str {}
+    impl Sealed for [u8] {}
+}
+
+/// The kind of text an [`AutomataRegex`] searches: `str` or `[u8]`
+pub trait Haystack: sealed::Sealed + ToOwned {
+    /// Whether the regex must only match valid UTF-8
+    #[doc(hidden)]
+    const UTF8: bool;
+    #[doc(hidden)]
+    fn as_bytes(&self) -> &[u8];
+    #[doc(hidden)]
+    fn slice(&self, range: Range<usize>) -> &Self;
+    #[doc(hidden)]
+    fn new_owned(capacity: usize) -> Self::Owned;
+    #[doc(hidden)]
+    fn push(dst: &mut Self::Owned, s: &Self);
+    /// Append the replacement to dst, with the `$name` references expanded
+    #[doc(hidden)]
+    fn interpolate(
+        caps: &captures::Captures,
+        haystack: &Self,
+        replacement: &Self,
+        dst: &mut Self::Owned,
+    );
+    /// Return the position of the char following the one at pos
+    #[doc(hidden)]
+    fn next_pos(&self, pos: usize) -> Option<usize>;
+    #[doc(hidden)]
+    fn remove_range(&self, range: Option<Range<usize>>) -> Cow<'_, Self>;
+    #[doc(hidden)]
+    fn remove_all_ranges(&self, ranges: impl Iterator<Item = Range<usize>>) -> Cow<'_, Self>;
+}
+
+impl Haystack for str {
+    const UTF8: bool = true;
+    fn as_bytes(&self) -> &[u8] {
+        str::as_bytes(self)
+    }
+    fn slice(&self, range: Range<usize>) -> &Self {
+        &self[range]
+    }
+    fn new_owned(capacity: usize) -> String {
+        String::with_capacity(capacity)
+    }
+    fn push(dst: &mut String, s: &Self) {
+        dst.push_str(s);
+    }
+    fn interpolate(
+        caps: &captures::Captures,
+        haystack: &Self,
+        replacement: &Self,
+        dst: &mut String,
+    ) {
+        caps.interpolate_string_into(haystack, replacement, dst);
+    }
+    fn next_pos(&self, pos: usize) -> Option<usize> {
+        self[pos..].chars().next().map(|c| pos + c.len_utf8())
+    }
+    fn remove_range(&self, range: Option<Range<usize>>) -> Cow<'_, Self> {
+        crate::remove::remove_range(self, range)
+    }
+    fn remove_all_ranges(&self, ranges: impl Iterator<Item = Range<usize>>) -> Cow<'_, Self> {
+        crate::remove::remove_all_ranges(self, ranges)
+    }
+}
+
+impl Haystack for [u8] {
+    const UTF8: bool = false;
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+    fn slice(&self, range: Range<usize>) -> &Self {
+        &self[range]
+    }
+    fn new_owned(capacity: usize) -> Vec<u8> {
+        Vec::with_capacity(capacity)
+    }
+    fn push(dst: &mut Vec<u8>, s: &Self) {
+        dst.extend_from_slice(s);
+    }
+    fn interpolate(
+        caps: &captures::Captures,
+        haystack: &Self,
+        replacement: &Self,
+        dst: &mut Vec<u8>,
+    ) {
+        caps.interpolate_bytes_into(haystack, replacement, dst);
+    }
+    fn next_pos(&self, pos: usize) -> Option<usize> {
+        Some(pos + 1).filter(|&pos| pos <= self.len())
+    }
+    fn remove_range(&self, range: Option<Range<usize>>) -> Cow<'_, Self> {
+        crate::remove::bytes_remove_range(self, range)
+    }
+    fn remove_all_ranges(&self, ranges: impl Iterator<Item = Range<usize>>) -> Cow<'_, Self> {
+        crate::remove::bytes_remove_all_ranges(self, ranges)
+    }
+}
+
+/// A replacement for the matches of an [`AutomataRegex`]: either a
+/// string in which `$name` references are expanded, or a closure
+/// receiving the captured groups
+pub trait AutomataReplacer<H: Haystack + ?Sized> {
+    /// Append the replacement of the match to dst
+    fn replace_append(&mut self, caps: &AutomataCaptures<'_, H>, dst: &mut H::Owned);
+}
+
+impl AutomataReplacer<str> for &str {
+    fn replace_append(&mut self, caps: &AutomataCaptures<'_, str>, dst: &mut String) {
+        str::interpolate(&caps.caps, caps.haystack, self, dst);
+    }
+}
+
+impl AutomataReplacer<str> for String {
+    fn replace_append(&mut self, caps: &AutomataCaptures<'_, str>, dst: &mut String) {
+        str::interpolate(&caps.caps, caps.haystack, self, dst);
+    }
+}
+
+impl AutomataReplacer<[u8]> for &[u8] {
+    fn replace_append(&mut self, caps: &AutomataCaptures<'_, [u8]>, dst: &mut Vec<u8>) {
+        <[u8]>::interpolate(&caps.caps, caps.haystack, self, dst);
+    }
+}
+
+impl<const N: usize> AutomataReplacer<[u8]> for &[u8; N] {
+    fn replace_append(&mut self, caps: &AutomataCaptures<'_, [u8]>, dst: &mut Vec<u8>) {
+        <[u8]>::interpolate(&caps.caps, caps.haystack, &self[..], dst);
+    }
+}
+
+impl<H, F, T> AutomataReplacer<H> for F
+where
+    H: Haystack + ?Sized,
+    F: FnMut(&AutomataCaptures<'_, H>) -> T,
+    T: AsRef<H>,
+{
+    fn replace_append(&mut self, caps: &AutomataCaptures<'_, H>, dst: &mut H::Owned) {
+        H::push(dst, self(caps).as_ref());
+    }
+}
+
+/// A configurable builder for an [`AutomataRegex`] or
+/// a [`BytesAutomataRegex`]
+#[derive(Debug, Clone)]
+pub struct AutomataRegexBuilder {
+    pattern: String,
+    syntax: syntax::Config,
+    meta: meta::Config,
+    longest: bool,
+    full: bool,
+}
+
+impl AutomataRegexBuilder {
+    pub fn new(pattern: &str) -> Self {
+        Self {
+            pattern: pattern.to_string(),
+            syntax: syntax::Config::new(),
+            meta: meta::Config::new(),
+            longest: false,
+            full: false,
+        }
+    }
+    pub fn case_insensitive(&mut self, yes: bool) -> &mut Self {
+        self.syntax = self.syntax.case_insensitive(yes);
+        self
+    }
+    pub fn multi_line(&mut self, yes: bool) -> &mut Self {
+        self.syntax = self.syntax.multi_line(yes);
+        self
+    }
+    pub fn dot_matches_new_line(&mut self, yes: bool) -> &mut Self {
+        self.syntax = self.syntax.dot_matches_new_line(yes);
+        self
+    }
+    pub fn ignore_whitespace(&mut self, yes: bool) -> &mut Self {
+        self.syntax = self.syntax.ignore_whitespace(yes);
+        self
+    }
+    pub fn swap_greed(&mut self, yes: bool) -> &mut Self {
+        self.syntax = self.syntax.swap_greed(yes);
+        self
+    }
+    pub fn crlf(&mut self, yes: bool) -> &mut Self {
+        self.syntax = self.syntax.crlf(yes);
+        self
+    }
+    pub fn line_terminator(&mut self, byte: u8) -> &mut Self {
+        self.syntax = self.syntax.line_terminator(byte);
+        self
+    }
+    pub fn unicode(&mut self, yes: bool) -> &mut Self {
+        self.syntax = self.syntax.unicode(yes);
+        self
+    }
+    pub fn octal(&mut self, yes: bool) -> &mut Self {
+        self.syntax = self.syntax.octal(yes);
+        self
+    }
+    pub fn nest_limit(&mut self, limit: u32) -> &mut Self {
+        self.syntax = self.syntax.nest_limit(limit);
+        self
+    }
+    /// Set the approximate size limit, in bytes, of the compiled regex
+    pub fn size_limit(&mut self, bytes: usize) -> &mut Self {
+        self.meta = self.meta.clone().nfa_size_limit(Some(bytes));
+        self
+    }
+    /// Set the approximate capacity, in bytes, of the cache of
+    /// transitions of the lazy DFA
+    pub fn dfa_size_limit(&mut self, bytes: usize) -> &mut Self {
+        self.meta = self.meta.clone().hybrid_cache_capacity(bytes);
+        self
+    }
+    /// Use leftmost-longest semantics (as POSIX engines do): among the
+    /// matches starting at the leftmost position, the longest one wins,
+    /// instead of the first one in the pattern's order of preference
+    pub fn longest(&mut self, yes: bool) -> &mut Self {
+        self.longest = yes;
+        self
+    }
+    /// Only accept matches spanning the whole haystack, as if the
+    /// pattern were wrapped in `^(?:...)$` but without the alternation
+    /// preference making a shorter branch fail the whole match
+    pub fn full(&mut self, yes: bool) -> &mut Self {
+        self.full = yes;
+        self
+    }
+    /// Build the regex, for `&str` or `&[u8]` haystacks depending
+    /// on the required type
+    pub fn build<H: Haystack + ?Sized>(&self) -> Result<AutomataRegex<H>, BuildError> {
+        let syntax = self.syntax.utf8(H::UTF8);
+        let build = |kind| {
+            meta::Builder::new()
+                .configure(self.meta.clone().match_kind(kind).utf8_empty(H::UTF8))
+                .syntax(syntax)
+                .build(&self.pattern)
+        };
+        let first = build(MatchKind::LeftmostFirst)?;
+        let all = if self.longest || self.full {
+            Some(build(MatchKind::All)?)
+        } else {
+            None
+        };
+        Ok(AutomataRegex {
+            pattern: self.pattern.clone(),
+            syntax,
+            meta: self.meta.clone(),
+            first,
+            all,
+            overlapping: OnceLock::new(),
+            reverse: OnceLock::new(),
+            longest: self.longest,
+            full: self.full,
+            haystack: PhantomData,
+        })
+    }
+}
+
+/// A regular expression built on the regex-automata crate, for the
+/// match semantics the regex crate doesn't provide.
+///
+/// It's what you get from `regex!` and the other macros when the
+/// `L` flag asks for leftmost-longest semantics, or the `F` flag
+/// for full-match semantics:
+///
+/// ```
+/// # use lazy_regex::*;
+/// let r: &AutomataRegex = regex!("sam|samwise"L);
+/// assert_eq!(r.find("samwise").unwrap().as_str(), "samwise");
+/// ```
+///
+/// Its API is a subset of the one of `regex::Regex`.
+pub struct AutomataRegex<H: Haystack + ?Sized = str> {
+    pattern: String,
+    syntax: syntax::Config,
+    meta: meta::Config,
+    /// searches with the usual leftmost-first semantics, used
+    /// to find where matches start
+    first: meta::Regex,
+    /// searches with the "all" semantics, used to extend a match
+    /// to the longest possible one, when leftmost-longest or
+    /// full-match is required
+    all: Option<meta::Regex>,
+    /// the engines of the overlapping search, built at its first use
+    overlapping: OnceLock<OverlappingEngines>,
+    /// the engine of the backward search, built at its first use, or
+    /// `None` if it couldn't be built
+    reverse: OnceLock<Option<ReverseEngine>>,
+    longest: bool,
+    full: bool,
+    haystack: PhantomData<fn(&H)>,
+}
+
+/// The engines finding all the matches starting at a position
+#[derive(Debug, Clone)]
+struct OverlappingEngines {
+    /// a lazy DFA with the "all" semantics, reporting every end of a
+    /// match in one pass, or `None` if it couldn't be built
+    dfa: Option<hybrid::DFA>,
+    /// searches with the "all" semantics, used when the lazy DFA gives
+    /// up (it does so on a non ASCII char when the pattern has a Unicode
+    /// word boundary): each search finds the longest match ending
+    /// before the previous one
+    all: meta::Regex,
+}
+
+type CachePoolFn = Box<dyn Fn() -> hybrid::Cache + Send + Sync + UnwindSafe + RefUnwindSafe>;
+
+/// A lazy DFA compiled from the reversed pattern, which finds where
+/// the last match starts by scanning the haystack from its end
+struct ReverseEngine {
+    dfa: hybrid::DFA,
+    caches: Pool<hybrid::Cache, CachePoolFn>,
+}
+
+impl<H: Haystack + ?Sized> Clone for AutomataRegex<H> {
+    fn clone(&self) -> Self {
+        Self {
+            pattern: self.pattern.clone(),
+            syntax: self.syntax,
+            meta: self.meta.clone(),
+            first: self.first.clone(),
+            all: self.all.clone(),
+            overlapping: self.overlapping.clone(),
+            // the pool of caches can't be cloned, so the
+            // clone builds its own reverse DFA when needed
+            reverse: OnceLock::new(),
+            longest: self.longest,
+            full: self.full,
+            haystack: PhantomData,
+        }
+    }
+}
+
+/// An [`AutomataRegex`] for `&[u8]` haystacks
+pub type BytesAutomataRegex = AutomataRegex<[u8]>;
+
+impl<H: Haystack + ?Sized> AutomataRegex<H> {
+    /// Build a regex with the default options
+    pub fn new(pattern: &str) -> Result<Self, BuildError> {
+        AutomataRegexBuilder::new(pattern).build()
+    }
+    /// Return the pattern this regex was built from
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+    /// Return the number of groups, including the implicit one of the whole match
+    pub fn captures_len(&self) -> usize {
+        self.first.captures_len()
+    }
+    fn search(&self, input: &Input<'_>) -> Option<regex_automata::Match> {
+        if self.full {
+            // the only possible match is the whole haystack
+            if input.start() != 0 {
+                return None;
+            }
+            let all = self.all.as_ref()?;
+            let m = all.search(&input.clone().anchored(Anchored::Yes))?;
+            return (m.end() == input.end()).then_some(m);
+        }
+        let m = self.first.search(input)?;
+        match &self.all {
+            Some(all) => {
+                // the longest match starting at the leftmost position
+                let input = input
+                    .clone()
+                    .range(m.start()..input.end())
+                    .anchored(Anchored::Yes);
+                all.search(&input)
+            }
+            None => Some(m),
+        }
+    }
+    /// Return the groups of a match whose span is already known
+    fn captures_in<'h>(&self, haystack: &'h H, range: Range<usize>) -> AutomataCaptures<'h, H> {
+        let input = Input::new(haystack.as_bytes())
+            .range(range.clone())
+            .anchored(Anchored::Yes);
+        if !self.longest {
+            // the groups are the ones of the preferred branch, if
+            // this branch covers the whole span
+            let mut caps = self.first.create_captures();
+            self.first.search_captures(&input, &mut caps);
+            if caps.get_match().map(|m| m.end()) == Some(range.end) {
+                return AutomataCaptures { haystack, caps };
+            }
+        }
+        let all = self.all.as_ref().unwrap_or(&self.first);
+        let mut caps = all.create_captures();
+        all.search_captures(&input, &mut caps);
+        AutomataCaptures { haystack, caps }
+    }
+    fn overlapping_engines(&self) -> &OverlappingEngines {
+        self.overlapping.get_or_init(|| {
+            let dfa = hybrid::DFA::builder()
+                .configure(
+                    hybrid::Config::new()
+                        .match_kind(MatchKind::All)
+                        .unicode_word_boundary(true)
+                        .cache_capacity(self.meta.get_hybrid_cache_capacity()),
+                )
+                .syntax(self.syntax)
+                .thompson(
+                    thompson::Config::new()
+                        .utf8(H::UTF8)
+                        .nfa_size_limit(self.meta.get_nfa_size_limit()),
+                )
+                .build(&self.pattern)
+                .ok();
+            let all = self.all.clone().unwrap_or_else(|| {
+                meta::Builder::new()
+                    .configure(self.meta.clone().match_kind(MatchKind::All).utf8_empty(H::UTF8))
+                    .syntax(self.syntax)
+                    .build(&self.pattern)
+                    .expect("regex already built with the same configuration")
+            });
+            OverlappingEngines { dfa, all }
+        })
+    }
+    /// Return the start of the last match, found by scanning the haystack
+    /// backward from its end, or `Err` if the reverse DFA couldn't be built
+    /// or gave up (it does so on a non ASCII char when the pattern has a
+    /// Unicode word boundary)
+    fn last_match_start(&self, haystack: &[u8]) -> Result<Option<usize>, ()> {
+        let reverse = self.reverse.get_or_init(|| {
+            let dfa = hybrid::DFA::builder()
+                .configure(
+                    hybrid::Config::new()
+                        .unicode_word_boundary(true)
+                        .cache_capacity(self.meta.get_hybrid_cache_capacity()),
+                )
+                .syntax(self.syntax)
+                .thompson(
+                    thompson::Config::new()
+                        .reverse(true)
+                        .utf8(H::UTF8)
+                        .nfa_size_limit(self.meta.get_nfa_size_limit()),
+                )
+                .build(&self.pattern)
+                .ok()?;
+            let create_dfa = dfa.clone();
+            let create: CachePoolFn = Box::new(move || create_dfa.create_cache());
+            Some(ReverseEngine {
+                dfa,
+                caches: Pool::new(create),
+            })
+        });
+        let reverse = reverse.as_ref().ok_or(())?;
+        let mut cache = reverse.caches.get();
+        reverse
+            .dfa
+            .try_search_rev(&mut cache, &Input::new(haystack))
+            .map(|hm| hm.map(|hm| hm.offset()))
+            .map_err(|_| ())
+    }
+    /// Tell whether there's a match in the haystack
+    pub fn is_match(&self, haystack: &H) -> bool {
+        if self.full {
+            self.search(&Input::new(haystack.as_bytes())).is_some()
+        } else {
+            self.first.is_match(haystack.as_bytes())
+        }
+    }
+    /// Return the leftmost match, if any
+    pub fn find<'h>(&self, haystack: &'h H) -> Option<AutomataMatch<'h, H>> {
+        self.search(&Input::new(haystack.as_bytes()))
+            .map(|m| AutomataMatch::new(haystack, m.range()))
+    }
+    /// Return an iterator over the successive non-overlapping matches
+    pub fn find_iter<'r, 'h>(&'r self, haystack: &'h H) -> AutomataMatches<'r, 'h, H> {
+        AutomataMatches {
+            regex: self,
+            haystack,
+            searcher: Searcher::new(Input::new(haystack.as_bytes())),
+        }
+    }
+    /// Return an iterator over all the matches, including the overlapping
+    /// ones: for every char position, in order, all the matches starting
+    /// there, from the shortest to the longest.
+    ///
+    /// With full-match semantics, the only match is the whole haystack.
+    pub fn find_overlapping<'r, 'h>(&'r self, haystack: &'h H) -> AutomataOverlapping<'r, 'h, H> {
+        let engines = self.overlapping_engines();
+        AutomataOverlapping {
+            regex: self,
+            haystack,
+            cache: engines.dfa.as_ref().map(|dfa| dfa.create_cache()),
+            start: Some(0),
+            state: OverlappingState::start(),
+            last_end: None,
+            ends: None,
+        }
+    }
+    /// Return the last match, if any.
+    ///
+    /// The haystack is searched backward from its end, with a reverse
+    /// lazy DFA, to find where the last match starts. An anchored forward
+    /// search from there then gives its end, as `find` would.
+    ///
+    /// When matches overlap, this isn't always the last match of
+    /// `find_iter`: `\d{2}` finds `"23"` in `"123"`, where the
+    /// iteration gives `"12"`.
+    /// When the reverse DFA gives up (on a non ASCII char, when the
+    /// pattern has a Unicode word boundary), the last match of
+    /// `find_iter` is returned.
+    pub fn rfind<'h>(&self, haystack: &'h H) -> Option<AutomataMatch<'h, H>> {
+        if self.full {
+            // the only possible match is the whole haystack
+            return self.find(haystack);
+        }
+        let bytes = haystack.as_bytes();
+        let start = match self.last_match_start(bytes) {
+            Ok(start) => start?,
+            Err(()) => return self.find_iter(haystack).last(),
+        };
+        let input = Input::new(bytes).range(start..).anchored(Anchored::Yes);
+        match self.search(&input) {
+            Some(m) => Some(AutomataMatch::new(haystack, m.range())),
+            // an empty match splitting a char, refused by the forward search
+            None => self.find_iter(haystack).last(),
+        }
+    }
+    /// Return the groups captured by the leftmost match, if any
+    pub fn captures<'h>(&self, haystack: &'h H) -> Option<AutomataCaptures<'h, H>> {
+        if self.all.is_none() {
+            let mut caps = self.first.create_captures();
+            self.first.search_captures(&Input::new(haystack.as_bytes()), &mut caps);
+            if !caps.is_match() {
+                return None;
+            }
+            return Some(AutomataCaptures { haystack, caps });
+        }
+        self.find(haystack).map(|m| self.captures_in(haystack, m.range()))
+    }
+    /// Return the groups captured by the last match, the one
+    /// [`rfind`](Self::rfind) returns, if any
+    pub fn rcaptures<'h>(&self, haystack: &'h H) -> Option<AutomataCaptures<'h, H>> {
+        self.rfind(haystack).map(|m| self.captures_in(haystack, m.range()))
+    }
+    /// Return an iterator over the groups captured by the successive
+    /// non-overlapping matches
+    pub fn captures_iter<'r, 'h>(&'r self, haystack: &'h H) -> AutomataCapturesIter<'r, 'h, H> {
+        AutomataCapturesIter {
+            matches: self.find_iter(haystack),
+        }
+    }
+    /// Replace at most `limit` matches (all of them if `limit` is 0)
+    /// with the replacement
+    pub fn replacen<'h, R: AutomataReplacer<H>>(
+        &self,
+        haystack: &'h H,
+        limit: usize,
+        mut replacement: R,
+    ) -> Cow<'h, H> {
+        let mut it = self.captures_iter(haystack).enumerate().peekable();
+        if it.peek().is_none() {
+            return Cow::Borrowed(haystack);
+        }
+        let len = haystack.as_bytes().len();
+        let mut dst = H::new_owned(len);
+        let mut last = 0;
+        for (i, caps) in it {
+            if limit > 0 && i >= limit {
+                break;
+            }
+            let m = caps.caps.get_match().expect("captures of a match");
+            H::push(&mut dst, haystack.slice(last..m.start()));
+            replacement.replace_append(&caps, &mut dst);
+            last = m.end();
+        }
+        H::push(&mut dst, haystack.slice(last..len));
+        Cow::Owned(dst)
+    }
+    /// Replace the leftmost match with the replacement
+    pub fn replace<'h, R: AutomataReplacer<H>>(&self, haystack: &'h H, replacement: R) -> Cow<'h, H> {
+        self.replacen(haystack, 1, replacement)
+    }
+    /// Replace all the non-overlapping matches with the replacement
+    pub fn replace_all<'h, R: AutomataReplacer<H>>(&self, haystack: &'h H, replacement: R) -> Cow<'h, H> {
+        self.replacen(haystack, 0, replacement)
+    }
+    /// Remove the leftmost match
+    pub fn remove_match<'h>(&self, haystack: &'h H) -> Cow<'h, H> {
+        haystack.remove_range(self.find(haystack).map(|m| m.range()))
+    }
+    /// Remove all the non-overlapping matches
+    pub fn remove_all_matches<'h>(&self, haystack: &'h H) -> Cow<'h, H> {
+        haystack.remove_all_ranges(self.find_iter(haystack).map(|m| m.range()))
+    }
+}
+
+impl<H: Haystack + ?Sized> fmt::Debug for AutomataRegex<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AutomataRegex").field(&self.pattern).finish()
+    }
+}
+
+/// A match of an [`AutomataRegex`]
+pub struct AutomataMatch<'h, H: Haystack + ?Sized> {
+    haystack: &'h H,
+    start: usize,
+    end: usize,
+}
+
+impl<'h, H: Haystack + ?Sized> AutomataMatch<'h, H> {
+    pub(crate) fn new(haystack: &'h H, range: Range<usize>) -> Self {
+        Self {
+            haystack,
+            start: range.start,
+            end: range.end,
+        }
+    }
+    pub fn start(&self) -> usize {
+        self.start
+    }
+    pub fn end(&self) -> usize {
+        self.end
+    }
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+impl<'h> AutomataMatch<'h, str> {
+    /// Return the matched part of the haystack
+    pub fn as_str(&self) -> &'h str {
+        &self.haystack[self.range()]
+    }
+}
+
+impl<'h> AutomataMatch<'h, [u8]> {
+    /// Return the matched part of the haystack
+    pub fn as_bytes(&self) -> &'h [u8] {
+        &self.haystack[self.range()]
+    }
+}
+
+impl<H: Haystack + ?Sized> Clone for AutomataMatch<'_, H> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<H: Haystack + ?Sized> Copy for AutomataMatch<'_, H> {}
+
+impl<H: Haystack + ?Sized> fmt::Debug for AutomataMatch<'_, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AutomataMatch")
+            .field("start", &self.start)
+            .field("end", &self.end)
+            .finish()
+    }
+}
+
+/// The groups captured by a match of an [`AutomataRegex`]
+pub struct AutomataCaptures<'h, H: Haystack + ?Sized> {
+    haystack: &'h H,
+    caps: captures::Captures,
+}
+
+impl<'h, H: Haystack + ?Sized> AutomataCaptures<'h, H> {
+    /// Return the group at the given index, the group 0 being the whole match
+    pub fn get(&self, i: usize) -> Option<AutomataMatch<'h, H>> {
+        self.caps
+            .get_group(i)
+            .map(|span| AutomataMatch::new(self.haystack, span.range()))
+    }
+    /// Return the group with the given name
+    pub fn name(&self, name: &str) -> Option<AutomataMatch<'h, H>> {
+        self.caps
+            .get_group_by_name(name)
+            .map(|span| AutomataMatch::new(self.haystack, span.range()))
+    }
+    /// Return the number of groups, including the whole match
+    pub fn len(&self) -> usize {
+        self.caps.group_len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<H: Haystack + ?Sized> fmt::Debug for AutomataCaptures<'_, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AutomataCaptures").field(&self.caps).finish()
+    }
+}
+
+/// An iterator over the non-overlapping matches of an [`AutomataRegex`]
+pub struct AutomataMatches<'r, 'h, H: Haystack + ?Sized> {
+    regex: &'r AutomataRegex<H>,
+    haystack: &'h H,
+    searcher: Searcher<'h>,
+}
+
+impl<'h, H: Haystack + ?Sized> Iterator for AutomataMatches<'_, 'h, H> {
+    type Item = AutomataMatch<'h, H>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let regex = self.regex;
+        self.searcher
+            .advance(|input| Ok(regex.search(input)))
+            .map(|m| AutomataMatch::new(self.haystack, m.range()))
+    }
+}
+
+impl<H: Haystack + ?Sized> fmt::Debug for AutomataMatches<'_, '_, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AutomataMatches").field("regex", self.regex).finish()
+    }
+}
+
+/// An iterator over the groups captured by the non-overlapping
+/// matches of an [`AutomataRegex`]
+pub struct AutomataCapturesIter<'r, 'h, H: Haystack + ?Sized> {
+    matches: AutomataMatches<'r, 'h, H>,
+}
+
+impl<'h, H: Haystack + ?Sized> Iterator for AutomataCapturesIter<'_, 'h, H> {
+    type Item = AutomataCaptures<'h, H>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let m = self.matches.next()?;
+        Some(self.matches.regex.captures_in(m.haystack, m.range()))
+    }
+}
+
+impl<H: Haystack + ?Sized> fmt::Debug for AutomataCapturesIter<'_, '_, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AutomataCapturesIter").field("regex", self.matches.regex).finish()
+    }
+}
+
+/// An iterator over the overlapping matches of an [`AutomataRegex`]
+pub struct AutomataOverlapping<'r, 'h, H: Haystack + ?Sized> {
+    regex: &'r AutomataRegex<H>,
+    haystack: &'h H,
+    cache: Option<hybrid::Cache>,
+    /// the start of the matches being reported
+    start: Option<usize>,
+    state: OverlappingState,
+    /// the end of the last match reported for this start
+    last_end: Option<usize>,
+    /// the ends not yet reported for this start, from the last one,
+    /// when the lazy DFA gave up
+    ends: Option<Vec<usize>>,
+}
+
+impl<H: Haystack + ?Sized> AutomataOverlapping<'_, '_, H> {
+    /// Return the end of the next match starting at `start`
+    fn next_end(&mut self, start: usize) -> Option<usize> {
+        let engines = self.regex.overlapping_engines();
+        let input = Input::new(self.haystack.as_bytes())
+            .range(start..)
+            .anchored(Anchored::Yes);
+        if self.ends.is_none() {
+            if let (Some(dfa), Some(cache)) = (&engines.dfa, &mut self.cache) {
+                if dfa.try_search_overlapping_fwd(cache, &input, &mut self.state).is_ok() {
+                    return self.state.get_match().map(|m| m.offset());
+                }
+            }
+            // the ends are found from the last one, with one search each
+            let mut ends = Vec::new();
+            let mut limit = input.end();
+            while let Some(m) = engines.all.search(&input.clone().range(start..limit)) {
+                if self.last_end.is_some_and(|last_end| m.end() <= last_end) {
+                    break;
+                }
+                ends.push(m.end());
+                if m.end() == start {
+                    break;
+                }
+                limit = m.end() - 1;
+            }
+            self.ends = Some(ends);
+        }
+        self.ends.as_mut()?.pop()
+    }
+}
+
+impl<'h, H: Haystack + ?Sized> Iterator for AutomataOverlapping<'_, 'h, H> {
+    type Item = AutomataMatch<'h, H>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.regex.full {
+            self.start.take()?;
+            return self.regex.find(self.haystack);
+        }
+        loop {
+            let start = self.start?;
+            if let Some(end) = self.next_end(start) {
+                self.last_end = Some(end);
+                return Some(AutomataMatch::new(self.haystack, start..end));
+            }
+            self.start = self.haystack.next_pos(start);
+            self.state = OverlappingState::start();
+            self.last_end = None;
+            self.ends = None;
+        }
+    }
+}
+
+impl<H: Haystack + ?Sized> fmt::Debug for AutomataOverlapping<'_, '_, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AutomataOverlapping").field("regex", self.regex).finish()
+    }
+}
@@ -0,0 +1,74 @@
+use super::regex;
+
+/// The bits of the flags taken by [`build_regex`], which the macros
+/// combine in the code they generate
+#[doc(hidden)]
+pub mod flag {
+    /// `i`
+    pub const CASE_INSENSITIVE: u8 = 1;
+    /// `m`
+    pub const MULTI_LINE: u8 = 1 << 1;
+    /// `s`
+    pub const DOT_MATCHES_NEW_LINE: u8 = 1 << 2;
+    /// `x`
+    pub const IGNORE_WHITESPACE: u8 = 1 << 3;
+    /// `U`
+    pub const SWAP_GREED: u8 = 1 << 4;
+}
+
+/// Build a regex with the given flags, for the macros whose regex
+/// has no other option.
+///
+/// The bits of `flags` are the ones of the [`flag`] module. Calling
+/// this function, which isn't generic, rather than inlining the
+/// builder calls at every call site, keeps the binary smaller.
+#[doc(hidden)]
+pub fn build_regex(pattern: &str, flags: u8) -> regex::Regex {
+    regex::RegexBuilder::new(pattern)
+        .case_insensitive(flags & flag::CASE_INSENSITIVE != 0)
+        .multi_line(flags & flag::MULTI_LINE != 0)
+        .dot_matches_new_line(flags & flag::DOT_MATCHES_NEW_LINE != 0)
+        .ignore_whitespace(flags & flag::IGNORE_WHITESPACE != 0)
+        .swap_greed(flags & flag::SWAP_GREED != 0)
+        .build()
+        .unwrap_or_else(|e| build_failed(pattern, &e))
+}
+
+/// Build a bytes regex with the given flags, as [`build_regex`] does
+#[doc(hidden)]
+#[cfg(not(feature = "lite"))]
+pub fn build_bytes_regex(pattern: &str, flags: u8) -> regex::bytes::Regex {
+    regex::bytes::RegexBuilder::new(pattern)
+        .case_insensitive(flags & flag::CASE_INSENSITIVE != 0)
+        .multi_line(flags & flag::MULTI_LINE != 0)
+        .dot_matches_new_line(flags & flag::DOT_MATCHES_NEW_LINE != 0)
+        .ignore_whitespace(flags & flag::IGNORE_WHITESPACE != 0)
+        .swap_greed(flags & flag::SWAP_GREED != 0)
+        .build()
+        .unwrap_or_else(|e| build_failed(pattern, &e))
+}
+
+/// Return a builder of the pattern, for the macros whose regex has
+/// options.
+///
+/// The macros don't call `RegexBuilder::new` themselves, as clippy would
+/// then check the literal pattern without the options it was checked with.
+#[doc(hidden)]
+pub fn regex_builder(pattern: &str) -> regex::RegexBuilder {
+    regex::RegexBuilder::new(pattern)
+}
+
+/// Return a builder of the bytes pattern, as [`regex_builder`] does
+#[doc(hidden)]
+#[cfg(not(feature = "lite"))]
+pub fn bytes_regex_builder(pattern: &str) -> regex::bytes::RegexBuilder {
+    regex::bytes::RegexBuilder::new(pattern)
+}
+
+/// Panic on a regex failing to build, which can only happen when a
+/// limit is exceeded as the pattern was checked at compile time
+#[cold]
+#[inline(never)]
+fn build_failed(pattern: &str, error: &regex::Error) -> ! {
+    panic!("failed to build the regex {pattern:?}: {error}")
+}
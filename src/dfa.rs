@@ -0,0 +1,109 @@
+use {
+    crate::automata::{
+        AutomataMatch,
+        AutomataRegex,
+        Haystack,
+    },
+    regex_automata::dfa::{
+        dense,
+        regex,
+    },
+    std::fmt,
+};
+
+/// Bytes aligned as `u32`, as needed to deserialize a DFA without copy
+#[doc(hidden)]
+#[repr(C)]
+pub struct AlignedDfaBytes<B: ?Sized> {
+    pub align: [u32; 0],
+    pub bytes: B,
+}
+
+/// A regular expression whose DFAs were built at compile time,
+/// so that it's ready as soon as they're deserialized.
+///
+/// It's what the [`dfa_regex!`](crate::dfa_regex) and
+/// [`dfa_regex_is_match!`](crate::dfa_regex_is_match) macros build:
+///
+/// ```
+/// # use lazy_regex::*;
+/// let r: &DfaRegex = dfa_regex!(r"\d+");
+/// assert_eq!(r.find("lazy-regex 3").unwrap().as_str(), "3");
+/// ```
+///
+/// When the DFAs couldn't be built, eg because they'd be too big, the
+/// regex is an [`AutomataRegex`] compiled at first use.
+pub struct DfaRegex<H: Haystack + ?Sized = str> {
+    engine: Engine<H>,
+}
+
+enum Engine<H: Haystack + ?Sized> {
+    Dfa {
+        pattern: &'static str,
+        regex: Box<regex::Regex<dense::DFA<&'static [u32]>>>,
+    },
+    Fallback(Box<AutomataRegex<H>>),
+}
+
+/// A [`DfaRegex`] for `&[u8]` haystacks
+pub type BytesDfaRegex = DfaRegex<[u8]>;
+
+impl<H: Haystack + ?Sized> DfaRegex<H> {
+    /// Deserialize the forward and reverse DFAs built by the macro
+    #[doc(hidden)]
+    pub fn from_dfa_bytes(pattern: &'static str, forward: &'static [u8], reverse: &'static [u8]) -> Self {
+        let forward = dense::DFA::from_bytes(forward)
+            .expect("forward DFA serialized by lazy-regex")
+            .0;
+        let reverse = dense::DFA::from_bytes(reverse)
+            .expect("reverse DFA serialized by lazy-regex")
+            .0;
+        Self {
+            engine: Engine::Dfa {
+                pattern,
+                regex: Box::new(regex::Builder::new().build_from_dfas(forward, reverse)),
+            },
+        }
+    }
+    /// Wrap the regex compiled at runtime, when the DFAs couldn't be built
+    #[doc(hidden)]
+    pub fn from_regex(regex: AutomataRegex<H>) -> Self {
+        Self {
+            engine: Engine::Fallback(Box::new(regex)),
+        }
+    }
+    /// Return the pattern this regex was built from
+    pub fn as_str(&self) -> &str {
+        match &self.engine {
+            Engine::Dfa { pattern, .. } => pattern,
+            Engine::Fallback(regex) => regex.as_str(),
+        }
+    }
+    /// Tell whether the DFAs were built at compile time, rather than
+    /// the regex being compiled at first use
+    pub fn is_precompiled(&self) -> bool {
+        matches!(self.engine, Engine::Dfa { .. })
+    }
+    /// Tell whether there's a match in the haystack
+    pub fn is_match(&self, haystack: &H) -> bool {
+        match &self.engine {
+            Engine::Dfa { regex, .. } => regex.is_match(haystack.as_bytes()),
+            Engine::Fallback(regex) => regex.is_match(haystack),
+        }
+    }
+    /// Return the leftmost match, if any
+    pub fn find<'h>(&self, haystack: &'h H) -> Option<AutomataMatch<'h, H>> {
+        match &self.engine {
+            Engine::Dfa { regex, .. } => regex
+                .find(haystack.as_bytes())
+                .map(|m| AutomataMatch::new(haystack, m.range())),
+            Engine::Fallback(regex) => regex.find(haystack),
+        }
+    }
+}
+
+impl<H: Haystack + ?Sized> fmt::Debug for DfaRegex<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DfaRegex").field(&self.as_str()).finish()
+    }
+}
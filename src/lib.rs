@@ -3,7 +3,7 @@
 With lazy-regex macros, regular expressions
 
 * are checked at compile time, with clear error messages
-* are wrapped in `once_cell` lazy static initializers so that they're compiled only once
+* are wrapped in lazy static initializers (from `once_cell` by default) so that they're compiled only once
 * can hold flags as suffix: `let case_insensitive_regex = regex!("ab*"i);`
 * are defined in a less verbose way
 
@@ -14,7 +14,9 @@ But most often, you won't even use the `regex!` macro but the other macros which
 * [Test a match](#test-a-match) with [`regex_is_match!`]
 * [Extract a value](#extract-a-value) with [`regex_find!`]
 * [Capture](#capture) with [`regex_captures!`]
+* [Get the last match](#get-the-last-match) with [`regex_rfind!`] and [`regex_rcaptures!`]
 * [Iter on captures](#iter-on-captures) with [`regex_captures_iter!`]
+* [Find overlapping matches](#find-overlapping-matches) with [`regex_find_overlapping!`]
 * [Replace with captured groups](#replace-with-captured-groups) with [`regex_replace!`] and [`regex_replace_all!`]
 * [Remove part(s) of a string](#remove-part-of-a-string) with [`regex_remove!`] and [`regex_remove_all!`]
 * [Switch over patterns](#switch-over-patterns) with [`regex_switch!`]
@@ -60,8 +62,10 @@ assert_eq!(r.is_match(r#" "Aristote" "Platon" "#), true);
 Build a regex that operates on `&[u8]`:
 ```rust
 # use lazy_regex::regex;
+# #[cfg(not(feature = "lite"))] {
 let r = regex!("(byte)?string$"B);
 assert_eq!(r.is_match(b"bytestring"), true);
+# }
 ```
 
 There's no problem using the multiline definition syntax:
@@ -77,11 +81,31 @@ assert_eq!(r.find("This is lazy_regex-2.2!").unwrap().as_str(), "lazy_regex-2.2"
 
 (look at the `regex_captures!` macro to easily extract the groups)
 
+The whitespace and comments of a verbose pattern (`x` flag) aren't kept in the binary: the regex is built from a minimal equivalent pattern, which is also what `as_str()` returns.
+
 This line doesn't compile because the regex is invalid:
 ```compile_fail
+# use lazy_regex::*;
 let r = regex!("(unclosed");
 
 ```
+The error points at the offending part of the pattern (precisely on nightly, by underlining it in the message on stable), with a hint at the fix for the most common mistakes, like look-around or an unescaped `{`:
+```compile_fail
+# use lazy_regex::*;
+// help: capture the part you want in a group instead, eg `foo(bar)` rather than `(?<=foo)bar`
+let r = regex!("(?<=foo)bar");
+```
+```compile_fail
+# use lazy_regex::*;
+// help: to match a literal `{`, escape it: `\{`
+let r = regex!("a{2");
+```
+```compile_fail
+# use lazy_regex::*;
+// help: `B` isn't an inline flag, give it as suffix of the literal, eg `"..."B`
+let r = regex!("(?B)a");
+```
+
 Supported regex flags: [`i`, `m`, `s`, `x`, `U`][regex::RegexBuilder], and you may also use `B` to build a bytes regex.
 
 The following regexes are equivalent:
@@ -92,15 +116,141 @@ The following regexes are equivalent:
 
 They're all case insensitive instances of `regex::bytes::Regex`.
 
+A bytes regex may also be given a byte string, whose non ASCII bytes match themselves, which is convenient for binary headers:
+```rust
+# use lazy_regex::regex;
+# #[cfg(not(feature = "lite"))] {
+let r = regex!(b"^\x89PNG\r\n\x1A\n");
+assert!(r.is_match(b"\x89PNG\r\n\x1A\n..."));
+# }
+```
+
+A class holding non ASCII bytes, eg `[\xFE\xFF]`, is matched with Unicode disabled, so that the bytes aren't taken as chars: its other items, like `\w`, are then ASCII only.
+
+The same pattern with the same flags and options may be used at several call sites: the regex is compiled only once, at the first use, and its compiled program is shared by all of them.
+
+## Builder options
+
+The other options of the [`RegexBuilder`] can be given after the literal, in `regex!`, `lazy_regex!`, and their `bytes_` variants.
+Boolean options may be given by name only, to enable them:
+
+```rust
+# use lazy_regex::regex;
+# #[cfg(not(feature = "lite"))] {
+let r = regex!(r"(?m)^\d+$", crlf, unicode = false, size_limit = 1 << 20);
+assert!(r.is_match("abc\r\n123\r\n"));
+# }
+```
+
+Available options are `case_insensitive`, `multi_line`, `dot_matches_new_line`, `ignore_whitespace`, `swap_greed`, `crlf`, `line_terminator`, `unicode`, `octal`, `nest_limit`, `size_limit`, and `dfa_size_limit`.
+The values must be literals, possibly combined with arithmetic or bitwise operators for the integer ones (eg `1 << 20`), as the regex is checked at compile time with exactly the options it will be built with.
+Paths to constants aren't accepted, as their values aren't known when the macro is expanded.
+
+```compile_fail
+# use lazy_regex::*;
+const LIMIT: usize = 1 << 20;
+let r = regex!("^a+$", size_limit = LIMIT); // error: expected an integer literal
+```
+
+## Patterns from fragments and files
+
+Instead of a literal, the pattern may be built with `concat!` and `include_str!`, possibly nested.
+As the macro reads them itself, they're the only macros accepted: a pattern given by another macro, eg one of your `macro_rules!`, is rejected.
+Unlike the standard `include_str!`, the file is relative to the crate's root (the directory of its `Cargo.toml`), not to the file containing the call.
+As there's no literal to hold them, the flags are then given with the `flags` option:
+
+```rust
+# use lazy_regex::regex;
+let r = regex!(concat!(r"^v", r"\d+", r"(\.\d+)*$"), flags = "i");
+assert!(r.is_match("V3.6"));
+```
+
+The resulting pattern is checked at compile time, as any other one.
+
+```compile_fail
+# use lazy_regex::*;
+macro_rules! version { () => { r"^v\d+$" } }
+let r = regex!(version!()); // error: only `concat!` and `include_str!` can be used to build a pattern
+```
+
+## Leftmost-longest semantics
+
+By default, when several alternatives match at the same position, the first one in the pattern wins.
+The `L` flag switches to leftmost-longest semantics, as in POSIX tools, so that the longest match wins:
+
+```rust
+# use lazy_regex::regex_find;
+# #[cfg(not(feature = "lite"))] {
+assert_eq!(regex_find!("sam|samwise", "samwise"), Some("sam"));
+assert_eq!(regex_find!("sam|samwise"L, "samwise"), Some("samwise"));
+# }
+```
+
+With this flag, the regex is an [`AutomataRegex`], which is built on the regex-automata crate and offers a subset of the API of `regex::Regex`.
+It can be used with all macros.
+
+## Full-match semantics
+
+The `F` flag makes the regex match only the whole text, so that you don't have to wrap it in `^...$`:
+
+```rust
+# use lazy_regex::regex_is_match;
+# #[cfg(not(feature = "lite"))] {
+assert!(regex_is_match!(r"\d+"F, "1234"));
+assert!(!regex_is_match!(r"\d+"F, "1234 and more"));
+# }
+```
+
+The pattern isn't rewritten: the search is anchored at the start of the text and the match must end at its end.
+As with `L`, the regex is an [`AutomataRegex`], and the flag works with all macros.
+
+In `regex_switch!` and `bytes_regex_switch!`, flags given before the arms apply to all of them:
+
+```rust
+# use lazy_regex::regex_switch;
+# #[cfg(not(feature = "lite"))] {
+let kind = regex_switch!("42", F,
+    r"\d+" => "number",
+    r"\w+" => "word",
+);
+assert_eq!(kind, Some("number"));
+# }
+```
+
+## Thread-local regexes
+
+A `regex::Regex` is shared by all threads, which take their search caches from a pool. When many threads hammer the same regex, they contend on this pool.
+With the `T` flag, each thread uses its own clone of the lazy static regex, with its own pool (the compiled regex itself isn't duplicated):
+
+```rust
+# use lazy_regex::*;
+assert!(regex_is_match!(r"^\d+$"T, "1234"));
+let (_, key, value) = regex_captures!(r"(\w+)=(\w+)"T, "a=b").unwrap();
+assert_eq!((key, value), ("a", "b"));
+```
+
+The flag is supported by the macros whose result doesn't borrow the regex: `regex_is_match!`, `regex_find!`, `regex_captures!`, `regex_replace!`, `regex_replace_all!`, `regex_remove!`, `regex_remove_all!`, their `bytes_` and `lite_` variants, and `regex!` and `bytes_regex!`, which then return a `&'static std::thread::LocalKey`:
+
+```rust
+# use lazy_regex::*;
+let digits = regex!(r"^\d+$"T);
+assert!(digits.with(|r| r.is_match("1234")));
+```
+
+It needs `std`, and can't be combined with the `L` and `F` flags.
+The `contention` benchmark (`cargo bench --bench contention`) compares both kinds of regexes with more and more threads.
+
 
 # Test a match
 
 ```rust
 # use lazy_regex::*;
+# #[cfg(not(feature = "lite"))] {
 let b = regex_is_match!("[ab]+", "car");
 assert_eq!(b, true);
 let b = bytes_regex_is_match!("[ab]+", b"car");
 assert_eq!(b, true);
+# }
 ```
 
 See [`regex_is_match!`]
@@ -110,10 +260,12 @@ See [`regex_is_match!`]
 
 ```rust
 # use lazy_regex::regex_find;
+# #[cfg(not(feature = "lite"))] {
 let f_word = regex_find!(r"\bf\w+\b", "The fox jumps.");
 assert_eq!(f_word, Some("fox"));
 let f_word = regex_find!(r"\bf\w+\b"B, b"The forest is silent.");
 assert_eq!(f_word, Some(b"forest" as &[u8]));
+# }
 ```
 
 See [`regex_find!`]
@@ -125,9 +277,10 @@ See [`regex_find!`]
 let (_, letter) = regex_captures!("([a-z])[0-9]+"i, "form A42").unwrap();
 assert_eq!(letter, "A");
 
+let text = String::from("This is lazy_regex-2.0!");
 let (whole, name, version) = regex_captures!(
     r"(\w+)-([0-9.]+)", // a literal regex
-    "This is lazy_regex-2.0!", // any expression
+    &text, // any expression
 ).unwrap();
 assert_eq!(whole, "lazy_regex-2.0");
 assert_eq!(name, "lazy_regex");
@@ -137,10 +290,74 @@ assert_eq!(version, "2.0");
 There's no limit to the size of the tuple.
 It's checked at compile time to ensure you have the right number of capturing groups.
 
+The groups may also be bound after the text, like in a match arm, for an expression whose value is returned in the option:
+
+```rust
+# use lazy_regex::regex_captures;
+let v = regex_captures!(r"(?<name>\w+)-([0-9.]+)", "lazy_regex-2.0" => (_, name, version) => format!("{name} v{version}"));
+assert_eq!(v.as_deref(), Some("lazy_regex v2.0"));
+```
+
+Without the expression, the value is the tuple of the bound names, or the name when there's only one:
+
+```rust
+# use lazy_regex::regex_captures;
+let v = regex_captures!(r"(?<name>\w+)-([0-9.]+)", "lazy_regex-2.0" => (_, name, version));
+assert_eq!(v, Some(("lazy_regex", "2.0")));
+```
+
+When the bindings don't match the groups, the error is clear:
+
+```compile_fail
+# use lazy_regex::regex_captures;
+// error: pattern has 3 groups (0: whole, 1: name, 2: -) but 2 bindings were given
+let v = regex_captures!(r"(?<name>\w+)-([0-9.]+)", "lazy_regex-2.0" => (name, version) => version);
+```
+
 You receive `""` for optional groups with no value.
 
 See [`regex_captures!`]
 
+## Literal texts
+
+When the text is also a literal, [`regex_is_match!`], [`regex_find!`] and [`regex_captures!`] (and their `bytes_` variants) are evaluated at compile time: no regex is built at runtime, and the result can be used in a constant.
+
+```rust
+# use lazy_regex::*;
+let version = regex_captures!(r"^v(\d+)$", "v12");
+assert_eq!(version, Some(("v12", "12")));
+```
+
+As a literal text which never matches is probably a mistake, `regex_find!` and `regex_captures!` then give a deprecation warning.
+It can be silenced with `#[allow(deprecated)]`, and denied to make it an error:
+
+```compile_fail
+# use lazy_regex::*;
+# #[cfg(feature = "lite")] compile_error!("the lite regexes are never evaluated at compile time");
+#[deny(deprecated)]
+let never = regex_find!(r"^v\d+$", "version 12");
+```
+
+# Get the last match
+
+Get the last match, or the groups of the last match, without iterating over all the matches,
+with the text searched backward from its end:
+
+```rust
+# use lazy_regex::*;
+# #[cfg(not(feature = "lite"))] {
+let version = regex_rfind!(r"\d+(\.\d+)*", "tools/1.2/lazy-regex-3.6.0.tar.gz");
+assert_eq!(version, Some("3.6.0"));
+let (_, hour, minute) = regex_rcaptures!(r"(\d{2}):(\d{2})", "from 10:42 to 11:07").unwrap();
+assert_eq!((hour, minute), ("11", "07"));
+# }
+```
+
+When matches overlap, the backward search doesn't always find the last one `find_iter` would give:
+in `"123"`, `\d{2}` finds `"23"` backward but `"12"` forward.
+
+See [`regex_rfind!`] and [`regex_rcaptures!`]
+
 # Iter on captures
 
 ```rust
@@ -160,6 +377,20 @@ assert_eq!(movies, vec![
 
 See [`regex_captures_iter!`]
 
+# Find overlapping matches
+
+Iterators like `find_iter` start each search after the previous match. When you need all matches, including the overlapping ones and the ones starting where another one starts, use [`regex_find_overlapping!`]:
+
+```rust
+# use lazy_regex::regex_find_overlapping;
+# #[cfg(not(feature = "lite"))] {
+let motifs: Vec<(usize, &str)> = regex_find_overlapping!("A[CT]A", "ACATACA")
+    .map(|m| (m.start(), m.as_str()))
+    .collect();
+assert_eq!(motifs, vec![(0, "ACA"), (2, "ATA"), (4, "ACA")]);
+# }
+```
+
 # Replace with captured groups
 
 The [`regex_replace!`] and [`regex_replace_all!`] macros bring once compilation and compilation time checks to the `replace` and `replace_all` functions.
@@ -276,39 +507,183 @@ Like for the other macros, the regex is static, checked at compile time, and laz
 
 See [`lazy_regex!`]
 
+## Several statics at once
+
+The [`regexes!`] macro declares several shared lazy static regexes, with their types deduced from the flags.
+Each static comes with a module of the same name holding the indices of its named groups:
+
+```rust
+# use lazy_regex::*;
+# #[cfg(not(feature = "lite"))] {
+regexes! {
+    pub EMAIL = r"^(?<user>[\w.+-]+)@(?<domain>[\w.-]+)$"i;
+    pub(crate) HEADER = r"^GIF8[79]a"B;
+}
+let caps = EMAIL.captures("someone@example.com").unwrap();
+assert_eq!(&caps[EMAIL::DOMAIN], "example.com");
+assert!(HEADER.is_match(b"GIF89a"));
+# }
+```
+
+The group layout of a regex is also available as constants, with [`regex_captures_len!`] and [`regex_group_names!`], the `CAPTURES_LEN` and `GROUP_NAMES` constants of the modules made by `regexes!`, or the `CAPTURES_LEN` constant of the [`CapturesLen`] trait, implemented by the type of the statics declared with `lazy_regex!` and the `typed` option, so that it can be checked in `const` assertions:
+
+```rust
+# use lazy_regex::*;
+const _: () = assert!(regex_captures_len!(r"(\w+)-(\d+)") == 3);
+assert_eq!(regex_group_names!(r"(?<name>\w+)-(\d+)"), [None, Some("name"), None]);
+static PAIR: Lazy<TypedRegex<2>> = lazy_regex!(r"(\w+)=(\w+)", typed);
+const _: () = assert!(<Lazy<TypedRegex<2>>>::CAPTURES_LEN == 3);
+```
+
+The `typed` option is required for `CapturesLen`: a `Lazy<Regex>` static, declared without it, doesn't implement the trait, as its number of groups isn't part of its type.
+
+```compile_fail
+# use lazy_regex::*;
+static WORD: Lazy<Regex> = lazy_regex!(r"(\w+)");
+const LEN: usize = <Lazy<Regex>>::CAPTURES_LEN; // error: `CapturesLen` isn't implemented
+```
+
+## Precompiled DFAs
+
+[`dfa_regex!`] and [`dfa_regex_is_match!`] build the DFAs of the pattern at compile time, with regex-automata, and embed them in the binary.
+At first use, the DFAs are only deserialized, without copy, instead of the pattern being compiled, which removes the latency spike of the first search in short-lived programs:
+
+```rust
+# use lazy_regex::*;
+# #[cfg(not(feature = "lite"))] {
+let date = dfa_regex!(r"^(\d{4})-(\d{2})-(\d{2})$");
+assert!(date.is_match("2026-10-18"));
+assert!(dfa_regex_is_match!("[ab]+"i, "CAR"));
+# }
+```
+
+The returned [`DfaRegex`] only offers `is_match` and `find`.
+Each DFA is limited to 1 MiB by default, which the `dfa_size_limit` option changes.
+When the DFAs can't be built, because they'd be bigger or because the pattern has a Unicode word boundary, the regex is compiled at first use instead, as with the other macros, which `is_precompiled` tells.
+
+## Typed regexes
+
+With the `typed` option, [`regex!`] and [`lazy_regex!`] return a [`TypedRegex`] (or a [`TypedBytesRegex`]), whose number of groups, not counting the whole match, is part of the type.
+Its `captures` method returns an array which can't be of the wrong size, so functions can require a regex with a given number of groups:
+
+```rust
+# use lazy_regex::*;
+fn parse_pair<'h>(r: &TypedRegex<2>, text: &'h str) -> Option<(&'h str, &'h str)> {
+    let [key, value] = r.captures(text)?;
+    Some((key, value))
+}
+assert_eq!(parse_pair(regex!(r"(\w+)=(\w+)", typed), "a=b"), Some(("a", "b")));
+```
+
+## Grammars
+
+When several regexes share fragments, define them as rules of a [`regex_grammar!`], in which a pattern refers to another rule with `(?&name)`:
+
+```rust
+# use lazy_regex::*;
+regex_grammar! {
+    octet = r"25[0-5]|2[0-4]\d|1?\d?\d";
+    pub ipv4 = r"^(?&octet)(\.(?&octet)){3}$";
+}
+assert!(IPV4.is_match("192.168.0.1"));
+```
+
+References are expanded at compile time, and each rule is exported both as a lazy static regex (`IPV4`) and as a constant holding its expanded pattern (`IPV4_PATTERN`).
+A referenced rule is matched with its own flags only, the ones of the referencing rule don't apply to it.
+
 */
 
+#![cfg_attr(not(any(feature = "std", feature = "lazy-lock", feature = "lite")), no_std)]
+
+extern crate alloc;
+
+#[cfg(all(feature = "regex-automata", any(feature = "std", feature = "lazy-lock"), not(feature = "lite")))]
+mod automata;
+mod builder;
+#[cfg(all(feature = "regex-automata", any(feature = "std", feature = "lazy-lock"), not(feature = "lite")))]
+mod dfa;
+#[cfg(all(feature = "perf-literal", not(feature = "lite")))]
+mod literal;
+#[cfg(all(feature = "once_cell", not(any(feature = "std", feature = "lazy-lock", feature = "lite"))))]
+mod race;
 mod remove;
+mod shared;
+mod typed;
 
 pub use {
     lazy_regex_proc_macros::{
         lazy_regex,
+        lite_lazy_regex,
+        lite_regex,
+        lite_regex_captures,
+        lite_regex_captures_iter,
+        lite_regex_find,
+        lite_regex_is_match,
+        lite_regex_replace,
+        lite_regex_replace_all,
         regex,
         regex_captures,
         regex_captures_iter,
+        regex_captures_len,
         regex_find,
+        regex_find_overlapping,
+        regex_grammar,
+        regex_group_names,
         regex_if,
         regex_is_match,
+        regex_rcaptures,
+        regex_remove,
+        regex_remove_all,
         regex_replace,
         regex_replace_all,
+        regex_rfind,
         regex_switch,
+        regexes,
+        dfa_regex,
+        dfa_regex_is_match,
         bytes_lazy_regex,
         bytes_regex,
         bytes_regex_captures,
         bytes_regex_find,
+        bytes_regex_find_overlapping,
         bytes_regex_if,
         bytes_regex_is_match,
+        bytes_regex_rcaptures,
+        bytes_regex_remove,
+        bytes_regex_remove_all,
         bytes_regex_replace,
         bytes_regex_replace_all,
+        bytes_regex_rfind,
         bytes_regex_switch,
     },
-    once_cell::sync::Lazy,
+    builder::{
+        build_regex,
+        flag,
+        regex_builder,
+    },
     remove::{
         remove_match,
         remove_all_matches,
     },
+    shared::shared,
+    typed::{
+        CapturesLen,
+        TypedRegex,
+    },
 };
 
+#[cfg(feature = "lazy-lock")]
+pub use std::sync::LazyLock as Lazy;
+
+#[cfg(all(any(feature = "std", feature = "lite"), not(feature = "lazy-lock")))]
+pub use once_cell::sync::Lazy;
+
+#[cfg(all(feature = "once_cell", not(any(feature = "std", feature = "lazy-lock", feature = "lite"))))]
+pub use race::Lazy;
+
+#[cfg(not(any(feature = "std", feature = "lazy-lock", feature = "lite", feature = "once_cell")))]
+compile_error!("without the `std` feature, lazy-regex needs the `once_cell` one for its lazy statics");
+
 #[cfg(not(feature = "lite"))]
 pub use {
     regex::{
@@ -319,10 +694,43 @@ pub use {
             RegexBuilder as BytesRegexBuilder
         },
     },
+    builder::{
+        build_bytes_regex,
+        bytes_regex_builder,
+    },
     remove::{
         bytes_remove_match,
         bytes_remove_all_matches,
     },
+    typed::TypedBytesRegex,
+};
+
+#[cfg(all(feature = "regex-automata", any(feature = "std", feature = "lazy-lock"), not(feature = "lite")))]
+pub use {
+    regex_automata,
+    automata::{
+        AutomataCaptures,
+        AutomataCapturesIter,
+        AutomataMatch,
+        AutomataMatches,
+        AutomataOverlapping,
+        AutomataRegex,
+        AutomataRegexBuilder,
+        AutomataReplacer,
+        BytesAutomataRegex,
+        Haystack,
+    },
+    dfa::{
+        AlignedDfaBytes,
+        BytesDfaRegex,
+        DfaRegex,
+    },
+};
+
+#[cfg(all(feature = "perf-literal", not(feature = "lite")))]
+pub use literal::{
+    BytesLiteralSearcher,
+    LiteralSearcher,
 };
 
 #[cfg(feature = "lite")]
@@ -333,79 +741,12 @@ pub use {
     },
 };
 
-/// Remove the first match of a regex from the text, returning a borrowed slice when possible
-///
-/// ```rust
-/// # use lazy_regex::regex_remove;
-/// let name = regex_remove!(
-///     r"-[0-9]+(\.[0-9]+)*$",
-///      "lazy-regex-3.5.2",
-/// );
-/// assert_eq!(name, "lazy-regex");
-/// assert!(matches!(name, std::borrow::Cow::Borrowed(_)));
-/// ```
-#[macro_export]
-macro_rules! regex_remove {
-    ($rex:tt, $text:expr $(,)?) => {{
-        let rex = $crate::regex!($rex);
-        $crate::remove_match(
-            &rex,
-            $text,
-        )
-    }};
-}
-
-#[macro_export]
-#[cfg(not(feature = "lite"))]
-macro_rules! bytes_regex_remove {
-    ($rex:tt, $text:expr $(,)?) => {{
-        let rex = $crate::bytes_regex!($rex);
-        $crate::bytes_remove_match(
-            &rex,
-            $text,
-        )
-    }};
-}
-
-/// Remove all matches of a regex from the text
-///
-/// ```rust
-/// # use lazy_regex::regex_remove_all;
-/// assert_eq!(
-///     regex_remove_all!(r"\s+", "    ab  c    d  e    "),
-///     "abcde"
-/// );
-/// ```
-///
-/// Whenever possible, no new string is allocated and a borrowed slice is returned, even when several matches are removed
-/// (if they're all either at the start or end of the text).
-///
-/// ```rust
-/// # use lazy_regex::regex_remove_all;
-/// let input = "154681string63731";
-/// let output = regex_remove_all!(r"\d", input);
-/// assert_eq!(output, "string");
-/// assert!(matches!(output, std::borrow::Cow::Borrowed("string")));
-/// ```
-#[macro_export]
-macro_rules! regex_remove_all {
-    ($rex:tt, $text:expr $(,)?) => {{
-        let rex = $crate::regex!($rex);
-        $crate::remove_all_matches(
-            &rex,
-            $text,
-        )
-    }};
-}
-
-#[macro_export]
-macro_rules! bytes_regex_remove_all {
-    ($rex:tt, $text:expr $(,)?) => {{
-        let rex = $crate::bytes_regex!($rex);
-        $crate::bytes_remove_all_matches(
-            &rex,
-            $text,
-        )
-    }};
-}
-
+#[cfg(feature = "regex-lite")]
+pub use {
+    regex_lite::{
+        self,
+        Captures as LiteCaptures,
+        Regex as LiteRegex,
+        RegexBuilder as LiteRegexBuilder,
+    },
+};
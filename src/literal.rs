@@ -0,0 +1,112 @@
+use {
+    aho_corasick::{
+        AhoCorasick,
+        AhoCorasickBuilder,
+        MatchKind,
+    },
+    alloc::borrow::Cow,
+    core::ops::Range,
+    memchr::memmem,
+};
+
+/// The search of one or several literals, for the macros whose
+/// pattern has no other regex feature
+// the searcher lives in a static, it's better not to box the finder
+#[allow(clippy::large_enum_variant)]
+enum Searcher {
+    /// a single literal, with the same case
+    One(memmem::Finder<'static>),
+    /// an alternation of literals, or a case insensitive literal
+    Many(AhoCorasick),
+}
+
+impl Searcher {
+    fn new(literals: &[&'static [u8]], ascii_case_insensitive: bool) -> Self {
+        match literals {
+            [literal] if !ascii_case_insensitive => Self::One(memmem::Finder::new(*literal)),
+            _ => Self::Many(
+                AhoCorasickBuilder::new()
+                    .match_kind(MatchKind::LeftmostFirst)
+                    .ascii_case_insensitive(ascii_case_insensitive)
+                    .build(literals)
+                    .expect("literals checked by lazy-regex"),
+            ),
+        }
+    }
+    fn is_match(&self, haystack: &[u8]) -> bool {
+        match self {
+            Self::One(finder) => finder.find(haystack).is_some(),
+            Self::Many(ac) => ac.is_match(haystack),
+        }
+    }
+    fn find(&self, haystack: &[u8]) -> Option<Range<usize>> {
+        match self {
+            Self::One(finder) => finder
+                .find(haystack)
+                .map(|start| start..start + finder.needle().len()),
+            Self::Many(ac) => ac.find(haystack).map(|m| m.range()),
+        }
+    }
+    fn find_iter<'s, 'h>(&'s self, haystack: &'h [u8]) -> impl Iterator<Item = Range<usize>> + 's
+    where
+        'h: 's,
+    {
+        let (one, many) = match self {
+            Self::One(finder) => {
+                let len = finder.needle().len();
+                (Some(finder.find_iter(haystack).map(move |start| start..start + len)), None)
+            }
+            Self::Many(ac) => (None, Some(ac.find_iter(haystack).map(|m| m.range()))),
+        };
+        one.into_iter().flatten().chain(many.into_iter().flatten())
+    }
+}
+
+/// The searcher of literals used instead of a regex by `regex_is_match!`,
+/// `regex_find!` and `regex_remove_all!` when the pattern is only made of
+/// literals, eg `"foo"` or `"foo|bar"i`
+#[doc(hidden)]
+pub struct LiteralSearcher {
+    searcher: Searcher,
+}
+
+impl LiteralSearcher {
+    pub fn new(literals: &[&'static [u8]], ascii_case_insensitive: bool) -> Self {
+        Self {
+            searcher: Searcher::new(literals, ascii_case_insensitive),
+        }
+    }
+    pub fn is_match(&self, haystack: &str) -> bool {
+        self.searcher.is_match(haystack.as_bytes())
+    }
+    pub fn find<'h>(&self, haystack: &'h str) -> Option<&'h str> {
+        // the literals are valid UTF-8, so they match on char boundaries
+        self.searcher.find(haystack.as_bytes()).map(|range| &haystack[range])
+    }
+    pub fn remove_all_matches<'h>(&self, haystack: &'h str) -> Cow<'h, str> {
+        crate::remove::remove_all_ranges(haystack, self.searcher.find_iter(haystack.as_bytes()))
+    }
+}
+
+/// The searcher of literals used instead of a bytes regex
+#[doc(hidden)]
+pub struct BytesLiteralSearcher {
+    searcher: Searcher,
+}
+
+impl BytesLiteralSearcher {
+    pub fn new(literals: &[&'static [u8]], ascii_case_insensitive: bool) -> Self {
+        Self {
+            searcher: Searcher::new(literals, ascii_case_insensitive),
+        }
+    }
+    pub fn is_match(&self, haystack: &[u8]) -> bool {
+        self.searcher.is_match(haystack)
+    }
+    pub fn find<'h>(&self, haystack: &'h [u8]) -> Option<&'h [u8]> {
+        self.searcher.find(haystack).map(|range| &haystack[range])
+    }
+    pub fn remove_all_matches<'h>(&self, haystack: &'h [u8]) -> Cow<'h, [u8]> {
+        crate::remove::bytes_remove_all_ranges(haystack, self.searcher.find_iter(haystack))
+    }
+}
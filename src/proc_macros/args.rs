@@ -1,26 +1,60 @@
-use syn::{
-    parse::{
-        Parse,
-        ParseStream,
-        Result,
+use {
+    crate::{
+        options::RegexOption,
+        pattern::PatternSource,
+    },
+    syn::{
+        parse::{
+            Parse,
+            ParseStream,
+            Result,
+        },
+        parse_quote,
+        Attribute,
+        Expr,
+        ExprClosure,
+        Ident,
+        LitByteStr,
+        LitStr,
+        Pat,
+        Token,
+        Visibility,
     },
-    Expr,
-    ExprClosure,
-    LitStr,
-    Token,
 };
 
+/// Wrapping of the arguments given to the `regex` and `lazy_regex`
+/// macros: the pattern, optionally followed by builder options
+pub(crate) struct RegexArgs {
+    pub regex_str: PatternSource,
+    pub options: Vec<RegexOption>,
+}
+
+impl Parse for RegexArgs {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let regex_str = input.parse::<PatternSource>()?;
+        let mut options = Vec::new();
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break; // allow a trailing comma
+            }
+            options.push(input.parse::<RegexOption>()?);
+        }
+        Ok(Self { regex_str, options })
+    }
+}
+
 /// Wrapping of the two arguments given to one of the
 /// `regex_is_match`, `regex_find`, or `regex_captures`
 /// macros
 pub(crate) struct RexValArgs {
-    pub regex_str: LitStr,
+    pub regex_str: PatternSource,
     pub value: Expr, // this expression is (or produces) the text to search or check
 }
 
 impl Parse for RexValArgs {
     fn parse(input: ParseStream<'_>) -> Result<Self> {
-        let regex_str = input.parse::<LitStr>()?;
+        let regex_str = input.parse::<PatternSource>()?;
         input.parse::<Token![,]>()?;
         let value = input.parse::<Expr>()?;
         let _ = input.parse::<Token![,]>(); // allow a trailing comma
@@ -28,10 +62,78 @@ impl Parse for RexValArgs {
     }
 }
 
+/// Wrapping of the arguments given to `regex_captures`: the pattern,
+/// the text, and optionally an arm binding the groups for an expression,
+/// eg `regex_captures!(r"(\w+)-(\d+)", text => (_, name, version) => version)`.
+///
+/// Without the expression, eg `text => (_, name, version)`, the arm's
+/// value is the tuple of the bound names.
+pub(crate) struct RexCapturesArgs {
+    pub regex_str: PatternSource,
+    pub value: Expr,
+    pub arm: Option<(Pat, Expr)>,
+}
+
+impl Parse for RexCapturesArgs {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let regex_str = input.parse::<PatternSource>()?;
+        input.parse::<Token![,]>()?;
+        let value = input.parse::<Expr>()?;
+        let arm = if input.peek(Token![=>]) {
+            input.parse::<Token![=>]>()?;
+            let bindings = Pat::parse_single(input)?;
+            let body = if input.peek(Token![=>]) {
+                input.parse::<Token![=>]>()?;
+                input.parse::<Expr>()?
+            } else {
+                bound_names_value(&bindings)
+            };
+            Some((bindings, body))
+        } else {
+            None
+        };
+        let _ = input.parse::<Token![,]>(); // allow a trailing comma
+        Ok(Self {
+            regex_str,
+            value,
+            arm,
+        })
+    }
+}
+
+/// Return the expression of the names bound by a pattern: the name
+/// when there's only one, or else the tuple of the names, in order
+fn bound_names_value(pat: &Pat) -> Expr {
+    fn collect<'p>(pat: &'p Pat, names: &mut Vec<&'p Ident>) {
+        match pat {
+            Pat::Ident(pat) => {
+                names.push(&pat.ident);
+                if let Some((_, sub)) = &pat.subpat {
+                    collect(sub, names);
+                }
+            }
+            Pat::Paren(pat) => collect(&pat.pat, names),
+            Pat::Reference(pat) => collect(&pat.pat, names),
+            Pat::Tuple(pat) => {
+                for pat in &pat.elems {
+                    collect(pat, names);
+                }
+            }
+            _ => {}
+        }
+    }
+    let mut names = Vec::new();
+    collect(pat, &mut names);
+    match names.as_slice() {
+        [name] => parse_quote!(#name),
+        names => parse_quote!((#(#names),*)),
+    }
+}
+
 /// Wrapping of the three arguments given to the
 /// `regex_replace` and `regex_replace_all` macros
 pub(crate) struct ReplaceArgs {
-    pub regex_str: LitStr,
+    pub regex_str: PatternSource,
     pub value: Expr,
     pub replacer: MaybeFun,
 }
@@ -43,7 +145,7 @@ pub(crate) enum MaybeFun {
 
 impl Parse for ReplaceArgs {
     fn parse(input: ParseStream<'_>) -> Result<Self> {
-        let regex_str = input.parse::<LitStr>()?;
+        let regex_str = input.parse::<PatternSource>()?;
         input.parse::<Token![,]>()?;
         let value = input.parse::<Expr>()?;
         input.parse::<Token![,]>()?;
@@ -65,14 +167,14 @@ impl Parse for ReplaceArgs {
 
 /// Wrapping of the arguments given to a `regex_if` macro
 pub(crate) struct RexIfArgs {
-    pub regex_str: LitStr,
+    pub regex_str: PatternSource,
     pub value: Expr, // this expression is (or produces) the text to search or check
     pub then: Expr,
 }
 
 impl Parse for RexIfArgs {
     fn parse(input: ParseStream<'_>) -> Result<Self> {
-        let regex_str = input.parse::<LitStr>()?;
+        let regex_str = input.parse::<PatternSource>()?;
         input.parse::<Token![,]>()?;
         let value = input.parse::<Expr>()?;
         input.parse::<Token![,]>()?;
@@ -89,10 +191,11 @@ impl Parse for RexIfArgs {
 /// Wrapping of the arguments given to a `regex_switch` macro
 pub(crate) struct RexSwitchArgs {
     pub value: Expr, // this expression is (or produces) the text to search or check
+    pub flags: Option<Ident>, // flags applying to all arms, eg `F`
     pub arms: Vec<RexSwitchArmArgs>,
 }
 pub(crate) struct RexSwitchArmArgs {
-    pub regex_str: LitStr,
+    pub regex_str: PatternSource,
     pub then: Expr,
 }
 
@@ -100,10 +203,17 @@ impl Parse for RexSwitchArgs {
     fn parse(input: ParseStream<'_>) -> Result<Self> {
         let value = input.parse::<Expr>()?;
         input.parse::<Token![,]>()?;
+        let flags = if input.peek(Ident) && input.peek2(Token![,]) {
+            let flags = input.parse::<Ident>()?;
+            input.parse::<Token![,]>()?;
+            Some(flags)
+        } else {
+            None
+        };
         let mut arms = Vec::new();
         loop {
             let lookahead = input.lookahead1();
-            if lookahead.peek(LitStr) {
+            if lookahead.peek(LitStr) || lookahead.peek(LitByteStr) || lookahead.peek(Ident) {
                 let arm = input.parse::<RexSwitchArmArgs>()?;
                 arms.push(arm);
             } else {
@@ -112,13 +222,14 @@ impl Parse for RexSwitchArgs {
         }
         Ok(Self {
             value,
+            flags,
             arms,
         })
     }
 }
 impl Parse for RexSwitchArmArgs {
     fn parse(input: ParseStream<'_>) -> Result<Self> {
-        let regex_str = input.parse::<LitStr>()?;
+        let regex_str = input.parse::<PatternSource>()?;
         input.parse::<Token![=>]>()?;
         let then = input.parse::<Expr>()?;
         let _ = input.parse::<Token![,]>(); // allow a trailing comma
@@ -128,3 +239,48 @@ impl Parse for RexSwitchArmArgs {
         })
     }
 }
+
+/// A named pattern, in a `regexes` or `regex_grammar` block, eg
+/// `pub IPV4 = r"(?&octet)(\.(?&octet)){3}"`
+pub(crate) struct NamedPattern {
+    pub attrs: Vec<Attribute>,
+    pub vis: Visibility,
+    pub name: Ident,
+    pub regex_str: PatternSource,
+}
+
+impl Parse for NamedPattern {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse::<Visibility>()?;
+        let name = input.parse::<Ident>()?;
+        input.parse::<Token![=]>()?;
+        let regex_str = input.parse::<PatternSource>()?;
+        Ok(Self {
+            attrs,
+            vis,
+            name,
+            regex_str,
+        })
+    }
+}
+
+/// Wrapping of the named patterns, separated with semicolons,
+/// given to a `regexes` or `regex_grammar` macro
+pub(crate) struct NamedPatterns {
+    pub items: Vec<NamedPattern>,
+}
+
+impl Parse for NamedPatterns {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let mut items = Vec::new();
+        while !input.is_empty() {
+            items.push(input.parse::<NamedPattern>()?);
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![;]>()?;
+        }
+        Ok(Self { items })
+    }
+}
@@ -0,0 +1,167 @@
+use {
+    crate::regex_code::{
+        RegexCode,
+        RegexInstance,
+    },
+    proc_macro2::{
+        Literal,
+        Span,
+        TokenStream as TokenStream2,
+    },
+    quote::quote,
+    regex_automata::{
+        dfa::{
+            dense,
+            regex::Builder,
+        },
+        nfa::thompson,
+        util::syntax,
+    },
+};
+
+/// The default limit of the size of each of the two DFAs embedded
+/// in the binary, overridden with the `dfa_size_limit` option
+const DEFAULT_DFA_SIZE_LIMIT: usize = 1 << 20;
+
+/// The forward and reverse DFAs of a pattern, serialized in both
+/// byte orders as the one of the target isn't known
+struct SerializedDfas {
+    forward_le: Vec<u8>,
+    forward_be: Vec<u8>,
+    reverse_le: Vec<u8>,
+    reverse_be: Vec<u8>,
+}
+
+impl RegexCode {
+    /// Build the lazy static initializer of a `DfaRegex` which, when
+    /// the DFAs of the pattern can be built within the size limit,
+    /// only has to deserialize them at runtime
+    pub fn dfa_build(&self) -> syn::Result<TokenStream2> {
+        let unsupported = |what: &str| {
+            Err(syn::Error::new(
+                Span::call_site(),
+                format!("{what} isn't available with the dfa macros"),
+            ))
+        };
+        if cfg!(feature = "lite") {
+            return unsupported("the `lite` feature of lazy-regex");
+        }
+        if self.is_automata() {
+            return unsupported("the L or F flag");
+        }
+        if self.local {
+            return unsupported("the T flag");
+        }
+        if self.typed {
+            return unsupported("the `typed` option");
+        }
+        let pattern = &self.pattern;
+        let tracking = self.tracking();
+        let Some(dfas) = self.serialize_dfas() else {
+            // the regex is compiled at first use, as with the other macros
+            let builder = self.automata_builder();
+            return Ok(quote! {
+                lazy_regex::Lazy::new(|| {
+                    #tracking
+                    lazy_regex::DfaRegex::from_regex(#builder.build().unwrap())
+                })
+            });
+        };
+        let aligned = |bytes: &[u8]| {
+            let bytes = Literal::byte_string(bytes);
+            quote!(&lazy_regex::AlignedDfaBytes { align: [], bytes: *#bytes })
+        };
+        let forward_le = aligned(&dfas.forward_le);
+        let forward_be = aligned(&dfas.forward_be);
+        let reverse_le = aligned(&dfas.reverse_le);
+        let reverse_be = aligned(&dfas.reverse_be);
+        Ok(quote! {
+            lazy_regex::Lazy::new(|| {
+                #tracking
+                #[cfg(target_endian = "little")]
+                static FORWARD: &lazy_regex::AlignedDfaBytes<[u8]> = #forward_le;
+                #[cfg(target_endian = "little")]
+                static REVERSE: &lazy_regex::AlignedDfaBytes<[u8]> = #reverse_le;
+                #[cfg(target_endian = "big")]
+                static FORWARD: &lazy_regex::AlignedDfaBytes<[u8]> = #forward_be;
+                #[cfg(target_endian = "big")]
+                static REVERSE: &lazy_regex::AlignedDfaBytes<[u8]> = #reverse_be;
+                lazy_regex::DfaRegex::from_dfa_bytes(#pattern, &FORWARD.bytes, &REVERSE.bytes)
+            })
+        })
+    }
+
+    /// Return the type of the `DfaRegex`, relative to the lazy_regex crate
+    pub fn dfa_regex_type(&self) -> TokenStream2 {
+        match self.regex {
+            RegexInstance::Regex(..) => quote!(DfaRegex),
+            RegexInstance::Bytes(..) => quote!(BytesDfaRegex),
+        }
+    }
+
+    /// Build and serialize the DFAs, or return `None` when they can't
+    /// be built, eg because they'd be too big or because the pattern
+    /// has a Unicode word boundary
+    fn serialize_dfas(&self) -> Option<SerializedDfas> {
+        let utf8 = matches!(self.regex, RegexInstance::Regex(..));
+        let flags = &self.flags;
+        let options = &self.options;
+        let mut syntax = syntax::Config::new()
+            .utf8(utf8)
+            .case_insensitive(flags.case_insensitive)
+            .multi_line(flags.multi_line)
+            .dot_matches_new_line(flags.dot_matches_new_line)
+            .ignore_whitespace(flags.ignore_whitespace)
+            .swap_greed(flags.swap_greed);
+        if let Some(yes) = options.crlf {
+            syntax = syntax.crlf(yes);
+        }
+        if let Some(byte) = options.line_terminator {
+            syntax = syntax.line_terminator(byte);
+        }
+        if let Some(yes) = options.unicode {
+            syntax = syntax.unicode(yes);
+        }
+        if let Some(yes) = options.octal {
+            syntax = syntax.octal(yes);
+        }
+        if let Some(limit) = options.nest_limit {
+            syntax = syntax.nest_limit(limit);
+        }
+        let limit = options.dfa_size_limit.unwrap_or(DEFAULT_DFA_SIZE_LIMIT);
+        let regex = Builder::new()
+            .syntax(syntax)
+            .thompson(thompson::Config::new().utf8(utf8).nfa_size_limit(options.size_limit))
+            .dense(dense::Config::new().dfa_size_limit(Some(limit)))
+            .build(&self.pattern)
+            .ok()?;
+        let serialize = |(bytes, padding): (Vec<u8>, usize)| bytes[padding..].to_vec();
+        Some(SerializedDfas {
+            forward_le: serialize(regex.forward().to_bytes_little_endian()),
+            forward_be: serialize(regex.forward().to_bytes_big_endian()),
+            reverse_le: serialize(regex.reverse().to_bytes_little_endian()),
+            reverse_be: serialize(regex.reverse().to_bytes_big_endian()),
+        })
+    }
+
+    /// Return the `AutomataRegexBuilder` configured with the flags and
+    /// options, to build the regex at runtime when there's no DFA
+    fn automata_builder(&self) -> TokenStream2 {
+        let pattern = &self.pattern;
+        let case_insensitive = self.flags.case_insensitive;
+        let multi_line = self.flags.multi_line;
+        let dot_matches_new_line = self.flags.dot_matches_new_line;
+        let ignore_whitespace = self.flags.ignore_whitespace;
+        let swap_greed = self.flags.swap_greed;
+        let option_calls = &self.options.calls;
+        quote! {
+            lazy_regex::AutomataRegexBuilder::new(#pattern)
+                .case_insensitive(#case_insensitive)
+                .multi_line(#multi_line)
+                .dot_matches_new_line(#dot_matches_new_line)
+                .ignore_whitespace(#ignore_whitespace)
+                .swap_greed(#swap_greed)
+                #option_calls
+        }
+    }
+}
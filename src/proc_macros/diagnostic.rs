@@ -0,0 +1,302 @@
+use {
+    crate::{
+        options::RegexOptions,
+        regex_code::RegexFlags,
+    },
+    proc_macro2::{
+        Literal,
+        Span,
+        TokenStream,
+    },
+    regex_syntax::{
+        ast::ErrorKind,
+        Parser,
+        ParserBuilder,
+    },
+    std::ops::Range,
+};
+
+/// An error in the syntax of a pattern, with the range of the
+/// offending part in the pattern and, when possible, a hint at
+/// how to fix it
+pub(crate) struct SyntaxError {
+    pub message: String,
+    /// the complete error, with the pattern and the offending part underlined
+    pub display: String,
+    pub range: Option<Range<usize>>,
+    pub help: Option<String>,
+}
+
+/// Return the parser of patterns into HIR, with the configuration
+/// the regex will have at runtime
+pub(crate) fn hir_parser(
+    is_bytes: bool,
+    flags: &RegexFlags,
+    options: &RegexOptions,
+) -> Parser {
+    let mut parser = ParserBuilder::new();
+    parser
+        .utf8(!is_bytes)
+        .case_insensitive(flags.case_insensitive)
+        .multi_line(flags.multi_line)
+        .dot_matches_new_line(flags.dot_matches_new_line)
+        .ignore_whitespace(flags.ignore_whitespace)
+        .swap_greed(flags.swap_greed);
+    if let Some(yes) = options.crlf {
+        parser.crlf(yes);
+    }
+    if let Some(byte) = options.line_terminator {
+        parser.line_terminator(byte);
+    }
+    if let Some(yes) = options.unicode {
+        parser.unicode(yes);
+    }
+    if let Some(yes) = options.octal {
+        parser.octal(yes);
+    }
+    if let Some(limit) = options.nest_limit {
+        parser.nest_limit(limit);
+    }
+    parser.build()
+}
+
+/// Parse the pattern with the configuration it will have at runtime,
+/// to get a precise syntax error
+pub(crate) fn check_syntax(
+    pattern: &str,
+    is_bytes: bool,
+    flags: &RegexFlags,
+    options: &RegexOptions,
+) -> Result<(), SyntaxError> {
+    let Err(e) = hir_parser(is_bytes, flags, options).parse(pattern) else {
+        return Ok(());
+    };
+    let (message, span, help) = match &e {
+        regex_syntax::Error::Parse(e) => (e.kind().to_string(), Some(e.span()), help(pattern, e)),
+        regex_syntax::Error::Translate(e) => (e.kind().to_string(), Some(e.span()), translate_help(e)),
+        _ => (e.to_string(), None, None),
+    };
+    Err(SyntaxError {
+        message,
+        display: e.to_string(),
+        range: span.map(|span| span.start.offset..span.end.offset),
+        help,
+    })
+}
+
+/// Suggest a fix for the most common errors
+fn help(pattern: &str, e: &regex_syntax::ast::Error) -> Option<String> {
+    let offending = &pattern[e.span().start.offset..];
+    match e.kind() {
+        ErrorKind::UnsupportedLookAround => Some(
+            "capture the part you want in a group instead, \
+            eg `foo(bar)` rather than `(?<=foo)bar`"
+                .to_string(),
+        ),
+        ErrorKind::RepetitionCountUnclosed | ErrorKind::RepetitionCountDecimalEmpty => {
+            Some("to match a literal `{`, escape it: `\\{`".to_string())
+        }
+        ErrorKind::RepetitionMissing if offending.starts_with('{') => {
+            Some("to match a literal `{`, escape it: `\\{`".to_string())
+        }
+        ErrorKind::FlagUnrecognized => {
+            let flag = offending.chars().next()?;
+            "BLFT".contains(flag).then(|| {
+                format!("`{flag}` isn't an inline flag, give it as suffix of the literal, eg `\"...\"{flag}`")
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Suggest enabling the features bringing the missing Unicode data,
+/// when they're not enabled
+fn translate_help(e: &regex_syntax::hir::Error) -> Option<String> {
+    use regex_syntax::hir::ErrorKind::*;
+    let features = [
+        ("unicode-age", cfg!(feature = "unicode-age")),
+        ("unicode-bool", cfg!(feature = "unicode-bool")),
+        ("unicode-case", cfg!(feature = "unicode-case")),
+        ("unicode-gencat", cfg!(feature = "unicode-gencat")),
+        ("unicode-perl", cfg!(feature = "unicode-perl")),
+        ("unicode-script", cfg!(feature = "unicode-script")),
+        ("unicode-segment", cfg!(feature = "unicode-segment")),
+    ];
+    let needed: &[&str] = match e.kind() {
+        UnicodePerlClassNotFound => &["unicode-perl"],
+        UnicodeCaseUnavailable => &["unicode-case"],
+        UnicodePropertyNotFound | UnicodePropertyValueNotFound => &[
+            "unicode-age",
+            "unicode-bool",
+            "unicode-gencat",
+            "unicode-script",
+            "unicode-segment",
+        ],
+        _ => return None,
+    };
+    let missing: Vec<&str> = features
+        .iter()
+        .filter(|(name, enabled)| !enabled && needed.contains(name))
+        .map(|(name, _)| *name)
+        .collect();
+    if missing.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "the Unicode data may be missing, enable the needed feature of lazy-regex among: {}",
+        missing.join(", "),
+    ))
+}
+
+/// Suggest a fix for a flag which isn't accepted as suffix
+pub(crate) fn suffix_flag_help(flag: char) -> Option<String> {
+    "Ru".contains(flag).then(|| {
+        format!("`{flag}` can't be given as suffix, set it inline at the start of the pattern: `(?{flag})`")
+    })
+}
+
+/// Build the error of a pattern whose syntax is invalid.
+///
+/// When the pattern is a literal, the error points at the offending
+/// part of it. As subspans of literals are only available on nightly,
+/// on stable the error points at the whole literal, and the message
+/// shows the pattern with the offending part underlined.
+pub(crate) fn syntax_error(
+    lit: Option<&Literal>,
+    tokens: &TokenStream,
+    e: SyntaxError,
+) -> syn::Error {
+    let subspan = lit
+        .zip(e.range)
+        .and_then(|(lit, range)| value_subspan(lit, range));
+    let mut message = match subspan {
+        Some(_) => format!("regex parse error: {}", e.message),
+        None => e.display,
+    };
+    if let Some(help) = e.help {
+        message.push_str("\n\nhelp: ");
+        message.push_str(&help);
+    }
+    match (subspan, lit) {
+        (Some(span), _) => syn::Error::new(span, message),
+        (None, Some(lit)) => syn::Error::new(lit.span(), message),
+        (None, None) => syn::Error::new_spanned(tokens, message),
+    }
+}
+
+/// Return the span of a part of the value of a string literal,
+/// or `None` when it can't be computed (subspans of literals are
+/// only available on nightly)
+fn value_subspan(lit: &Literal, range: Range<usize>) -> Option<Span> {
+    let source = lit.to_string();
+    let start = source_offset(&source, range.start)?;
+    let end = source_offset(&source, range.end)?;
+    lit.subspan(start..end.max(start + 1))
+}
+
+/// Compute the position, in the source of a string literal, of
+/// the char at the given offset of its value
+fn source_offset(source: &str, value_offset: usize) -> Option<usize> {
+    if let Some(raw) = source.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        return Some(1 + hashes + 1 + value_offset);
+    }
+    let quoted = source.strip_prefix('"')?;
+    let mut chars = quoted.char_indices().peekable();
+    let mut value_len = 0;
+    while let Some((i, ch)) = chars.next() {
+        if ch == '\\' && chars.next_if(|(_, ch)| *ch == '\n').is_some() {
+            // the line continuation skips the following whitespace
+            while chars.next_if(|(_, ch)| ch.is_whitespace()).is_some() {}
+            continue;
+        }
+        if value_len >= value_offset || ch == '"' {
+            return Some(1 + i);
+        }
+        if ch != '\\' {
+            value_len += ch.len_utf8();
+            continue;
+        }
+        match chars.next()?.1 {
+            'x' => {
+                chars.nth(1)?;
+                value_len += 1;
+            }
+            'u' => {
+                let mut code = String::new();
+                for (_, ch) in chars.by_ref() {
+                    match ch {
+                        '{' => {}
+                        '}' => break,
+                        _ => code.push(ch),
+                    }
+                }
+                let ch = u32::from_str_radix(&code, 16).ok().and_then(char::from_u32)?;
+                value_len += ch.len_utf8();
+            }
+            _ => value_len += 1,
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ast_error(pattern: &str) -> regex_syntax::ast::Error {
+        regex_syntax::ast::parse::Parser::new()
+            .parse(pattern)
+            .expect_err("the pattern should be invalid")
+    }
+
+    #[test]
+    fn test_source_offset() {
+        assert_eq!(source_offset(r#""a(b""#, 1), Some(2));
+        // escapes take several chars of the source for one of the value
+        assert_eq!(source_offset(r#""\\d{""#, 2), Some(4));
+        assert_eq!(source_offset(r#""\x41(""#, 1), Some(5));
+        assert_eq!(source_offset(r#""\u{e9}(""#, 2), Some(7));
+        assert_eq!(source_offset(r#""\"(""#, 1), Some(3));
+        // the end of the value is the closing quote
+        assert_eq!(source_offset(r#""ab""#, 2), Some(3));
+        // raw strings have no escape, only a prefix
+        assert_eq!(source_offset(r#"r"a(b""#, 1), Some(3));
+        assert_eq!(source_offset(r##"r#"a(b"#"##, 1), Some(4));
+        assert_eq!(source_offset(r##"r#"\d{"#"##, 2), Some(5));
+        // a line continuation skips the newline and the indentation
+        assert_eq!(source_offset("\"ab\\\n    (c\"", 2), Some(9));
+        assert_eq!(source_offset("\"ab\\\n    (c\"", 3), Some(10));
+    }
+
+    #[test]
+    fn test_help() {
+        let pattern = "(?<=a)b";
+        let hint = help(pattern, &ast_error(pattern)).unwrap();
+        assert!(hint.contains("rather than `(?<=foo)bar`"));
+        for pattern in ["a{", "a{,2}", "{"] {
+            let hint = help(pattern, &ast_error(pattern)).unwrap();
+            assert!(hint.contains("escape it: `\\{`"), "{pattern}: {hint}");
+        }
+        // the non-standard flags are given as suffix
+        for flag in ['B', 'L', 'F', 'T'] {
+            let pattern = format!("(?{flag})a");
+            let hint = help(&pattern, &ast_error(&pattern)).unwrap();
+            assert_eq!(
+                hint,
+                format!("`{flag}` isn't an inline flag, give it as suffix of the literal, eg `\"...\"{flag}`"),
+            );
+        }
+        let pattern = "(?Z)a";
+        assert_eq!(help(pattern, &ast_error(pattern)), None);
+        let pattern = "a)";
+        assert_eq!(help(pattern, &ast_error(pattern)), None);
+    }
+
+    #[test]
+    fn test_suffix_flag_help() {
+        assert!(suffix_flag_help('R').unwrap().contains("`(?R)`"));
+        assert!(suffix_flag_help('u').unwrap().contains("`(?u)`"));
+        assert_eq!(suffix_flag_help('z'), None);
+    }
+}
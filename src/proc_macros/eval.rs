@@ -0,0 +1,137 @@
+use {
+    crate::regex_code::{
+        RegexCode,
+        RegexInstance,
+    },
+    proc_macro2::{
+        Literal,
+        TokenStream as TokenStream2,
+    },
+    quote::quote,
+    syn::{
+        Expr,
+        ExprLit,
+        Lit,
+    },
+};
+
+/// The value of a haystack given as a literal, whose kind is the one
+/// the regex searches
+enum LiteralHaystack {
+    Str(String),
+    Bytes(Vec<u8>),
+}
+
+impl RegexCode {
+    /// Return the value of the haystack when it's a literal which
+    /// can be searched at compile time
+    fn literal_haystack(&self, value: &Expr) -> Option<LiteralHaystack> {
+        // the regex used at runtime must be the one we have here
+        if self.lite || cfg!(feature = "lite") || self.is_automata() {
+            return None;
+        }
+        let Expr::Lit(ExprLit { lit, .. }) = value else {
+            return None;
+        };
+        match (lit, &self.regex) {
+            (Lit::Str(s), RegexInstance::Regex(..)) => Some(LiteralHaystack::Str(s.value())),
+            (Lit::ByteStr(b), RegexInstance::Bytes(..)) => Some(LiteralHaystack::Bytes(b.value())),
+            _ => None,
+        }
+    }
+
+    /// Return whether the haystack matches, when it's a literal
+    pub fn eval_is_match(&self, value: &Expr) -> Option<TokenStream2> {
+        let is_match = match (self.literal_haystack(value)?, &self.regex) {
+            (LiteralHaystack::Str(s), RegexInstance::Regex(regex)) => regex.is_match(&s),
+            (LiteralHaystack::Bytes(b), RegexInstance::Bytes(regex)) => regex.is_match(&b),
+            _ => return None,
+        };
+        // behind a constant, the value isn't linted as a literal of the
+        // caller, eg by clippy in `assert!(regex_is_match!(..))`
+        let tracking = self.tracking();
+        Some(quote!({
+            #tracking
+            const IS_MATCH: bool = #is_match;
+            IS_MATCH
+        }))
+    }
+
+    /// Return the leftmost match, as a constant, when the haystack is a
+    /// literal, with a warning when there's none
+    pub fn eval_find(&self, value: &Expr) -> Option<TokenStream2> {
+        let found = match (self.literal_haystack(value)?, &self.regex) {
+            (LiteralHaystack::Str(s), RegexInstance::Regex(regex)) => {
+                regex.find(&s).map(|m| str_token(m.as_str()))
+            }
+            (LiteralHaystack::Bytes(b), RegexInstance::Bytes(regex)) => {
+                regex.find(&b).map(|m| bytes_token(m.as_bytes()))
+            }
+            _ => return None,
+        };
+        let item_type = self.item_type();
+        let value = match found {
+            Some(found) => quote!(Some(#found)),
+            None => never_matches(quote!(None::<#item_type>)),
+        };
+        Some(self.tracked(value))
+    }
+
+    /// Return the captured groups, as a constant tuple, when the haystack
+    /// is a literal, with a warning when there's no match
+    pub fn eval_captures(&self, value: &Expr) -> Option<TokenStream2> {
+        let groups: Option<Vec<TokenStream2>> = match (self.literal_haystack(value)?, &self.regex) {
+            (LiteralHaystack::Str(s), RegexInstance::Regex(regex)) => regex.captures(&s).map(|caps| {
+                caps.iter()
+                    .map(|c| str_token(c.map_or("", |c| c.as_str())))
+                    .collect()
+            }),
+            (LiteralHaystack::Bytes(b), RegexInstance::Bytes(regex)) => regex.captures(&b).map(|caps| {
+                caps.iter()
+                    .map(|c| bytes_token(c.map_or(&b""[..], |c| c.as_bytes())))
+                    .collect()
+            }),
+            _ => return None,
+        };
+        let value = match groups {
+            Some(groups) => quote!(Some((#(#groups),*))),
+            None => {
+                let item_type = self.item_type();
+                let item_types = (0..self.captures_len()).map(|_| &item_type);
+                never_matches(quote!(None::<(#(#item_types),*)>))
+            }
+        };
+        Some(self.tracked(value))
+    }
+
+    /// Return the type of the parts of a haystack
+    fn item_type(&self) -> TokenStream2 {
+        match self.regex {
+            RegexInstance::Regex(..) => quote!(&'static str),
+            RegexInstance::Bytes(..) => quote!(&'static [u8]),
+        }
+    }
+}
+
+fn str_token(s: &str) -> TokenStream2 {
+    quote!(#s)
+}
+
+fn bytes_token(b: &[u8]) -> TokenStream2 {
+    let b = Literal::byte_string(b);
+    quote!(#b as &[u8])
+}
+
+/// Wrap the value with a warning telling the literal haystack never
+/// matches, which is probably a mistake.
+///
+/// Proc macros can't emit warnings on stable, so this is done with
+/// the use of a deprecated constant.
+fn never_matches(value: TokenStream2) -> TokenStream2 {
+    quote! {{
+        #[deprecated(note = "the text is a literal which never matches the pattern")]
+        const NEVER_MATCHES: () = ();
+        let _ = NEVER_MATCHES;
+        #value
+    }}
+}
@@ -0,0 +1,155 @@
+use {
+    crate::args::NamedPattern,
+    regex_syntax::ast::{
+        parse::ParserBuilder,
+        ErrorKind,
+    },
+    std::collections::HashMap,
+};
+
+/// Find the `(?&name)` references in a pattern, returning their
+/// byte ranges and names
+fn references(pattern: &str) -> Vec<(std::ops::Range<usize>, &str)> {
+    let mut refs = Vec::new();
+    let bytes = pattern.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => {
+                i += 2; // an escaped char can't start a reference
+                continue;
+            }
+            b'(' if pattern[i..].starts_with("(?&") => {
+                if let Some(len) = pattern[i + 3..].find(')') {
+                    let end = i + 3 + len + 1;
+                    refs.push((i..end, &pattern[i + 3..end - 1]));
+                    i = end;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    refs
+}
+
+/// Return a name given to several capture groups of the pattern, as
+/// happens when a rule with a named group is referenced twice
+fn duplicate_group_name(pattern: &str, ignore_whitespace: bool) -> Option<&str> {
+    let error = ParserBuilder::new()
+        .ignore_whitespace(ignore_whitespace)
+        .build()
+        .parse(pattern)
+        .err()?;
+    match error.kind() {
+        ErrorKind::GroupNameDuplicate { .. } => {
+            let span = error.span();
+            pattern.get(span.start.offset..span.end.offset)
+        }
+        _ => None,
+    }
+}
+
+/// The rules of a `regex_grammar`, whose patterns may refer
+/// to other rules with `(?&name)`
+pub(crate) struct Grammar<'r> {
+    pub rules: &'r [NamedPattern],
+}
+
+impl Grammar<'_> {
+    /// Return the patterns of all rules, with their references replaced
+    /// with the (non capturing groups of) referenced patterns
+    pub fn expand(&self) -> syn::Result<Vec<String>> {
+        let indices: HashMap<String, usize> = self
+            .rules
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| (rule.name.to_string(), i))
+            .collect();
+        let mut expanded = vec![None; self.rules.len()];
+        for i in 0..self.rules.len() {
+            self.expand_rule(i, &indices, &mut expanded, &mut Vec::new())?;
+        }
+        Ok(expanded.into_iter().map(Option::unwrap).collect())
+    }
+    fn expand_rule(
+        &self,
+        i: usize,
+        indices: &HashMap<String, usize>,
+        expanded: &mut Vec<Option<String>>,
+        stack: &mut Vec<usize>,
+    ) -> syn::Result<()> {
+        if expanded[i].is_some() {
+            return Ok(());
+        }
+        let rule = &self.rules[i];
+        if let Some(pos) = stack.iter().position(|&j| j == i) {
+            let cycle: Vec<String> = stack[pos..]
+                .iter()
+                .chain(std::iter::once(&i))
+                .map(|&j| self.rules[j].name.to_string())
+                .collect();
+            return Err(syn::Error::new(
+                rule.name.span(),
+                format!("cyclic rule reference: {}", cycle.join(" -> ")),
+            ));
+        }
+        stack.push(i);
+        let pattern = &rule.regex_str.value;
+        let mut result = String::with_capacity(pattern.len());
+        let mut last = 0;
+        let references = references(pattern);
+        let has_references = !references.is_empty();
+        for (range, name) in references {
+            let Some(&j) = indices.get(name) else {
+                return Err(syn::Error::new_spanned(
+                    &rule.regex_str.tokens,
+                    format!("unknown rule {name:?}"),
+                ));
+            };
+            self.expand_rule(j, indices, expanded, stack)?;
+            result.push_str(&pattern[last..range.start]);
+            // the referenced rule is matched with its own flags only: the
+            // ones it doesn't have are turned off, so that the flags of the
+            // referencing rule don't apply to it
+            let suffix = self.rules[j]
+                .regex_str
+                .lit
+                .as_ref()
+                .map_or("", |lit| lit.suffix());
+            let (on, off): (String, String) = "imsxU".chars().partition(|ch| suffix.contains(*ch));
+            result.push_str("(?");
+            result.push_str(&on);
+            if !off.is_empty() {
+                result.push('-');
+                result.push_str(&off);
+            }
+            result.push(':');
+            result.push_str(expanded[j].as_deref().unwrap_or_default());
+            result.push(')');
+            last = range.end;
+        }
+        result.push_str(&pattern[last..]);
+        if has_references {
+            let ignore_whitespace = rule
+                .regex_str
+                .lit
+                .as_ref()
+                .is_some_and(|lit| lit.suffix().contains('x'));
+            if let Some(name) = duplicate_group_name(&result, ignore_whitespace) {
+                return Err(syn::Error::new(
+                    rule.name.span(),
+                    format!(
+                        "the group name {name:?} is duplicated in the expansion of `{}`: \
+                        a rule with named groups can only be referenced once",
+                        rule.name,
+                    ),
+                ));
+            }
+        }
+        stack.pop();
+        expanded[i] = Some(result);
+        Ok(())
+    }
+}
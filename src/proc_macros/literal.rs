@@ -0,0 +1,137 @@
+use {
+    crate::{
+        diagnostic::hir_parser,
+        regex_code::{
+            RegexCode,
+            RegexInstance,
+        },
+    },
+    proc_macro2::{
+        Literal,
+        TokenStream as TokenStream2,
+    },
+    quote::quote,
+    regex_syntax::{
+        hir::{
+            Class,
+            Hir,
+            HirKind,
+        },
+    },
+};
+
+/// The literals a pattern is an alternation of, when it has no other
+/// regex feature, so that it can be searched without building a regex
+pub(crate) struct Literals {
+    pub alternatives: Vec<Vec<u8>>,
+    /// whether the ASCII letters match both cases (`i` flag)
+    pub ascii_case_insensitive: bool,
+}
+
+impl RegexCode {
+    /// Return the literals the pattern is made of, or `None` when it
+    /// has regex features or when the literal fast path isn't enabled
+    pub fn literals(&self) -> Option<Literals> {
+        if !cfg!(feature = "literal") || cfg!(feature = "lite") || self.lite || self.is_automata() {
+            return None;
+        }
+        let is_bytes = matches!(self.regex, RegexInstance::Bytes(..));
+        let hir = hir_parser(is_bytes, &self.flags, &self.options)
+            .parse(&self.pattern)
+            .ok()?;
+        let branches = match hir.kind() {
+            HirKind::Alternation(branches) => branches.as_slice(),
+            _ => std::slice::from_ref(&hir),
+        };
+        let mut case_insensitive = None;
+        let mut alternatives = Vec::with_capacity(branches.len());
+        for branch in branches {
+            let mut literal = Vec::new();
+            if !push_literal(branch, &mut literal, &mut case_insensitive) || literal.is_empty() {
+                return None;
+            }
+            alternatives.push(literal);
+        }
+        Some(Literals {
+            alternatives,
+            ascii_case_insensitive: case_insensitive.unwrap_or(false),
+        })
+    }
+
+    /// Return the type of the literal searcher, relative to the lazy_regex crate
+    pub fn literal_searcher_type(&self) -> TokenStream2 {
+        match self.regex {
+            RegexInstance::Regex(..) => quote!(LiteralSearcher),
+            RegexInstance::Bytes(..) => quote!(BytesLiteralSearcher),
+        }
+    }
+
+    /// Build the static `RE` of a literal searcher, when the pattern
+    /// is made of literals
+    pub fn literal_statick(&self) -> Option<TokenStream2> {
+        let Literals {
+            alternatives,
+            ascii_case_insensitive,
+        } = self.literals()?;
+        let alternatives = alternatives.iter().map(|literal| Literal::byte_string(literal));
+        let searcher_type = self.literal_searcher_type();
+        let tracking = self.tracking();
+        Some(quote! {
+            static RE: lazy_regex::Lazy<lazy_regex:: #searcher_type > = lazy_regex::Lazy::new(|| {
+                #tracking
+                lazy_regex:: #searcher_type ::new(&[#(&#alternatives[..]),*], #ascii_case_insensitive)
+            });
+        })
+    }
+}
+
+/// Append the bytes matched by the hir to the literal, returning false
+/// if it can match something else than a single literal.
+///
+/// A letter matching its two ASCII cases, and no other char, is pushed
+/// in lowercase. As the letters must then all be case insensitive, or
+/// none of them, `case_insensitive` is set on the first one.
+fn push_literal(hir: &Hir, literal: &mut Vec<u8>, case_insensitive: &mut Option<bool>) -> bool {
+    match hir.kind() {
+        HirKind::Literal(lit) => {
+            if lit.0.iter().any(u8::is_ascii_alphabetic) && *case_insensitive.get_or_insert(false) {
+                return false;
+            }
+            literal.extend_from_slice(&lit.0);
+            true
+        }
+        HirKind::Class(class) => {
+            let Some(letter) = ascii_letter_of_both_cases(class) else {
+                return false;
+            };
+            if !*case_insensitive.get_or_insert(true) {
+                return false;
+            }
+            literal.push(letter);
+            true
+        }
+        HirKind::Capture(capture) => push_literal(&capture.sub, literal, case_insensitive),
+        HirKind::Concat(hirs) => hirs.iter().all(|hir| push_literal(hir, literal, case_insensitive)),
+        _ => false,
+    }
+}
+
+/// If the class is exactly an ASCII letter in both cases, eg `[Aa]`,
+/// return this letter in lowercase
+fn ascii_letter_of_both_cases(class: &Class) -> Option<u8> {
+    let (upper, lower) = match class {
+        Class::Unicode(class) => match class.ranges() {
+            [upper, lower] if upper.start() == upper.end() && lower.start() == lower.end() => {
+                (u8::try_from(upper.start()).ok()?, u8::try_from(lower.start()).ok()?)
+            }
+            _ => return None,
+        },
+        Class::Bytes(class) => match class.ranges() {
+            [upper, lower] if upper.start() == upper.end() && lower.start() == lower.end() => {
+                (upper.start(), lower.start())
+            }
+            _ => return None,
+        },
+    };
+    (upper.is_ascii_uppercase() && upper.to_ascii_lowercase() == lower).then_some(lower)
+}
@@ -1,11 +1,19 @@
 mod args;
+mod dfa;
+mod diagnostic;
+mod eval;
+mod grammar;
+mod literal;
+mod options;
+mod pattern;
 mod regex_code;
+mod verbose;
 
 use {
-    crate::{args::*, regex_code::*},
+    crate::{args::*, grammar::*, pattern::PatternSource, regex_code::*},
     proc_macro::TokenStream,
     quote::quote,
-    syn::{parse_macro_input, Expr},
+    syn::{parse_macro_input, Expr, Pat},
 };
 
 //  The following `process*` functions are convenience funcs
@@ -27,28 +35,116 @@ where
     F: Fn(RegexCode, Expr) -> T,
 {
     let parsed = parse_macro_input!(input as RexValArgs);
-    match RegexCode::from_lit_str(parsed.regex_str, as_bytes) {
+    match RegexCode::from_pattern(parsed.regex_str, as_bytes) {
         Ok(r) => f(r, parsed.value).into(),
         Err(e) => e.to_compile_error().into(),
     }
 }
 
+fn process_lite<T, F>(input: TokenStream, f: F) -> TokenStream
+where
+    T: Into<TokenStream>,
+    F: Fn(RegexCode) -> T,
+{
+    match RegexCode::from_token_stream_lite(input) {
+        Ok(r) => f(r).into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn process_with_value_lite<T, F>(input: TokenStream, f: F) -> TokenStream
+where
+    T: Into<TokenStream>,
+    F: Fn(RegexCode, Expr) -> T,
+{
+    let parsed = parse_macro_input!(input as RexValArgs);
+    match RegexCode::from_pattern_lite(parsed.regex_str) {
+        Ok(r) => f(r, parsed.value).into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Return the error of bindings not matching the groups of the
+/// pattern, unless they end with a rest pattern (`..`)
+fn bindings_error(regex_code: &RegexCode, bindings: &Pat) -> Option<syn::Error> {
+    let given = match bindings {
+        Pat::Tuple(tuple) if tuple.elems.iter().any(|p| matches!(p, Pat::Rest(_))) => None,
+        Pat::Tuple(tuple) => Some(tuple.elems.len()),
+        _ => Some(1),
+    };
+    regex_code.check_arity(given?, "binding", bindings)
+}
+
+fn captures<T, B, F>(input: TokenStream, build: B, f: F) -> TokenStream
+where
+    T: Into<TokenStream>,
+    B: Fn(PatternSource) -> syn::Result<RegexCode>,
+    F: Fn(RegexCode, Expr) -> T,
+{
+    let parsed = parse_macro_input!(input as RexCapturesArgs);
+    let regex_code = match build(parsed.regex_str) {
+        Ok(r) => r,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let Some((bindings, body)) = parsed.arm else {
+        return f(regex_code, parsed.value).into();
+    };
+    if let Some(e) = bindings_error(&regex_code, &bindings) {
+        return e.to_compile_error().into();
+    }
+    let captures: TokenStream = f(regex_code, parsed.value).into();
+    let captures = proc_macro2::TokenStream::from(captures);
+    quote! {
+        match #captures {
+            Some(#bindings) => Some(#body),
+            None => None,
+        }
+    }.into()
+}
+
 /// Return a lazy static Regex checked at compilation time and
 /// built at first use.
 ///
 /// Flags can be specified as suffix:
 /// ```
+/// # use lazy_regex::*;
 /// let case_insensitive_regex = regex!("^ab+$"i);
 /// ```
 ///
+/// Other builder options can be given after the literal:
+/// ```
+/// # use lazy_regex::*;
+/// let r = regex!("^ab+$", crlf, size_limit = 1 << 20);
+/// ```
+///
+/// The pattern may also be built with `concat!` and `include_str!`,
+/// with the flags given as an option:
+/// ```
+/// # use lazy_regex::*;
+/// let r = regex!(concat!("^ab", "+$"), flags = "i");
+/// ```
+/// As the macro reads them itself, they're the only macros accepted
+/// there: a `macro_rules!` macro of yours can't give the pattern.
+/// And the path given to `include_str!` is relative to the crate's
+/// root (the directory of its `Cargo.toml`), not to the calling file.
+///
 /// The macro returns a reference to a `regex::Regex`
 /// or a `regex::bytes::Regex` instance,
 /// differentiated by the `B` flag:
 /// ```
+/// # use lazy_regex::*;
 /// let verbose = regex!(r#"_([\d\.]+)"#)
 ///     .replace("This is lazy-regex_2.2", " (version $1)");
 /// assert_eq!(verbose, "This is lazy-regex (version 2.2)");
 /// ```
+///
+/// With the `T` flag, the macro returns a `&'static std::thread::LocalKey`
+/// giving each thread its own clone of the regex:
+/// ```
+/// # use lazy_regex::*;
+/// let r = regex!("^ab+$"T);
+/// assert!(r.with(|r| r.is_match("abb")));
+/// ```
 #[proc_macro]
 pub fn regex(input: TokenStream) -> TokenStream {
     process(input, false, |regex_code| regex_code.lazy_static())
@@ -59,9 +155,18 @@ pub fn regex(input: TokenStream) -> TokenStream {
 ///
 /// Flags can be specified as suffix:
 /// ```
+/// # use lazy_regex::*;
 /// let case_insensitive_regex = bytes_regex!("^ab+$"i);
 /// assert!(case_insensitive_regex.is_match(b"abB"));
 /// ```
+///
+/// The pattern may also be a byte string, whose non ASCII bytes
+/// match themselves:
+/// ```
+/// # use lazy_regex::*;
+/// let gif_or_jpeg = bytes_regex!(b"^(GIF8[79]a|\xFF\xD8\xFF)");
+/// assert!(gif_or_jpeg.is_match(b"\xFF\xD8\xFF\xE0"));
+/// ```
 #[proc_macro]
 pub fn bytes_regex(input: TokenStream) -> TokenStream {
     process(input, true, |regex_code| regex_code.lazy_static())
@@ -74,13 +179,24 @@ pub fn bytes_regex(input: TokenStream) -> TokenStream {
 /// Example:
 ///
 /// ```
+/// # use lazy_regex::*;
 /// pub static GLOBAL_REX: Lazy<Regex> = lazy_regex!("^ab+$"i);
 /// ```
 ///
 /// As for other macros, the regex is checked at compilation time.
+///
+/// With the `typed` option, the static is a `Lazy<TypedRegex<N>>`, whose
+/// number of groups is part of its type and given by the `CapturesLen` trait.
+/// This option is required for `CapturesLen`, which isn't implemented
+/// by an untyped `Lazy<Regex>`:
+/// ```
+/// # use lazy_regex::*;
+/// static PAIR: Lazy<TypedRegex<2>> = lazy_regex!(r"(\w+)=(\w+)", typed);
+/// const _: () = assert!(<Lazy<TypedRegex<2>>>::CAPTURES_LEN == 3);
+/// ```
 #[proc_macro]
 pub fn lazy_regex(input: TokenStream) -> TokenStream {
-    process(input, false, |regex_code| regex_code.build)
+    process(input, false, |regex_code| regex_code.lazy())
 }
 
 /// Return an instance of `once_cell::sync::Lazy<bytes::Regex>` that
@@ -89,13 +205,52 @@ pub fn lazy_regex(input: TokenStream) -> TokenStream {
 /// Example:
 ///
 /// ```
-/// pub static GLOBAL_REX: Lazy<bytes::Regex> = bytes_lazy_regex!("^ab+$"i);
+/// # use lazy_regex::*;
+/// pub static GLOBAL_REX: Lazy<BytesRegex> = bytes_lazy_regex!("^ab+$"i);
 /// ```
 ///
 /// As for other macros, the regex is checked at compilation time.
 #[proc_macro]
 pub fn bytes_lazy_regex(input: TokenStream) -> TokenStream {
-    process(input, true, |regex_code| regex_code.build)
+    process(input, true, |regex_code| regex_code.lazy())
+}
+
+/// Return the number of groups of the regex, including the implicit
+/// group of the whole match, as a constant computed at compile time.
+///
+/// Example:
+/// ```
+/// # use lazy_regex::*;
+/// const N: usize = regex_captures_len!(r"(\w+)-(\d+)");
+/// assert_eq!(N, 3);
+/// ```
+#[proc_macro]
+pub fn regex_captures_len(input: TokenStream) -> TokenStream {
+    process(input, false, |regex_code| {
+        let n = regex_code.captures_len();
+        regex_code.tracked(quote!(#n))
+    })
+}
+
+/// Return the names of the groups of the regex, as a constant
+/// `[Option<&'static str>; N]` array with `None` for the unnamed
+/// groups, including the implicit group of the whole match.
+///
+/// Example:
+/// ```
+/// # use lazy_regex::*;
+/// const NAMES: [Option<&str>; 3] = regex_group_names!(r"(?<name>\w+)-(\d+)");
+/// assert_eq!(NAMES, [None, Some("name"), None]);
+/// ```
+#[proc_macro]
+pub fn regex_group_names(input: TokenStream) -> TokenStream {
+    process(input, false, |regex_code| {
+        let names = regex_code.group_names().into_iter().map(|name| match name {
+            Some(name) => quote!(Some(#name)),
+            None => quote!(None),
+        });
+        regex_code.tracked(quote!([#(#names),*]))
+    })
 }
 
 /// Test whether an expression matches a lazy static
@@ -104,17 +259,33 @@ pub fn bytes_lazy_regex(input: TokenStream) -> TokenStream {
 ///
 /// Example:
 /// ```
+/// # use lazy_regex::*;
 /// let b = regex_is_match!("[ab]+", "car");
 /// assert_eq!(b, true);
 /// ```
+///
+/// As the text is a literal, the result above is computed at compile
+/// time. Any other expression is searched at runtime:
+/// ```
+/// # use lazy_regex::*;
+/// let word = String::from("car");
+/// assert!(regex_is_match!("[ab]+", &word));
+/// ```
 #[proc_macro]
 pub fn regex_is_match(input: TokenStream) -> TokenStream {
     process_with_value(input, false, |regex_code, value| {
-        let statick = regex_code.statick();
-        quote! {{
-            #statick;
+        if let Some(result) = regex_code.eval_is_match(&value) {
+            return result;
+        }
+        if let Some(statick) = regex_code.literal_statick() {
+            return quote! {{
+                #statick;
+                RE.is_match(#value)
+            }};
+        }
+        regex_code.scoped(quote! {
             RE.is_match(#value)
-        }}
+        })
     })
 }
 
@@ -124,17 +295,27 @@ pub fn regex_is_match(input: TokenStream) -> TokenStream {
 ///
 /// Example:
 /// ```
+/// # use lazy_regex::*;
 /// let b = bytes_regex_is_match!("[ab]+", b"car");
 /// assert_eq!(b, true);
+/// let word = b"car".to_vec();
+/// assert!(bytes_regex_is_match!("[ab]+", &word));
 /// ```
 #[proc_macro]
 pub fn bytes_regex_is_match(input: TokenStream) -> TokenStream {
     process_with_value(input, true, |regex_code, value| {
-        let statick = regex_code.statick();
-        quote! {{
-            #statick;
+        if let Some(result) = regex_code.eval_is_match(&value) {
+            return result;
+        }
+        if let Some(statick) = regex_code.literal_statick() {
+            return quote! {{
+                #statick;
+                RE.is_match(#value)
+            }};
+        }
+        regex_code.scoped(quote! {
             RE.is_match(#value)
-        }}
+        })
     })
 }
 
@@ -143,23 +324,39 @@ pub fn bytes_regex_is_match(input: TokenStream) -> TokenStream {
 ///
 /// Example:
 /// ```
+/// # use lazy_regex::*;
 /// let f_word = regex_find!(r#"\bf\w+\b"#, "The fox jumps.");
 /// assert_eq!(f_word, Some("fox"));
-/// let f_word = regex_find!(r#"\bf\w+\b"#B, "The forest is silent.");
+/// let f_word = regex_find!(r#"\bf\w+\b"#B, b"The forest is silent.");
 /// assert_eq!(f_word, Some(b"forest" as &[u8]));
 /// ```
+///
+/// As the texts are literals, the matches above are found at compile
+/// time. Any other expression is searched at runtime:
+/// ```
+/// # use lazy_regex::*;
+/// let text = format!("The {} jumps.", "fox");
+/// assert_eq!(regex_find!(r#"\bf\w+\b"#, &text), Some("fox"));
+/// ```
 #[proc_macro]
 pub fn regex_find(input: TokenStream) -> TokenStream {
     process_with_value(input, false, |regex_code, value| {
-        let statick = regex_code.statick();
+        if let Some(result) = regex_code.eval_find(&value) {
+            return result;
+        }
+        if let Some(statick) = regex_code.literal_statick() {
+            return quote! {{
+                #statick;
+                RE.find(#value)
+            }};
+        }
         let as_method = match regex_code.regex {
             RegexInstance::Regex(..) => quote!(as_str),
             RegexInstance::Bytes(..) => quote!(as_bytes),
         };
-        quote! {{
-            #statick;
+        regex_code.scoped(quote! {
             RE.find(#value).map(|mat| mat. #as_method ())
-        }}
+        })
     })
 }
 
@@ -168,21 +365,31 @@ pub fn regex_find(input: TokenStream) -> TokenStream {
 ///
 /// Example:
 /// ```
+/// # use lazy_regex::*;
 /// let f_word = bytes_regex_find!(r#"\bf\w+\b"#, b"The fox jumps.");
 /// assert_eq!(f_word, Some("fox".as_bytes()));
+/// let text = b"The fox jumps.".to_vec();
+/// assert_eq!(bytes_regex_find!(r#"\bf\w+\b"#, &text), Some("fox".as_bytes()));
 /// ```
 #[proc_macro]
 pub fn bytes_regex_find(input: TokenStream) -> TokenStream {
     process_with_value(input, true, |regex_code, value| {
-        let statick = regex_code.statick();
+        if let Some(result) = regex_code.eval_find(&value) {
+            return result;
+        }
+        if let Some(statick) = regex_code.literal_statick() {
+            return quote! {{
+                #statick;
+                RE.find(#value)
+            }};
+        }
         let as_method = match regex_code.regex {
             RegexInstance::Regex(..) => quote!(as_str),
             RegexInstance::Bytes(..) => quote!(as_bytes),
         };
-        quote! {{
-            #statick;
+        regex_code.scoped(quote! {
             RE.find(#value).map(|mat| mat. #as_method ())
-        }}
+        })
     })
 }
 
@@ -195,31 +402,57 @@ pub fn bytes_regex_find(input: TokenStream) -> TokenStream {
 ///
 /// Example:
 /// ```
+/// # use lazy_regex::*;
+/// let text = String::from("This is lazy_regex-2.0!");
 /// let (whole, name, version) = regex_captures!(
 ///     r#"(\w+)-([0-9.]+)"#, // a literal regex
-///     "This is lazy_regex-2.0!", // any expression
+///     &text, // any expression
 /// ).unwrap();
 /// assert_eq!(whole, "lazy_regex-2.0");
 /// assert_eq!(name, "lazy_regex");
 /// assert_eq!(version, "2.0");
 /// ```
+///
+/// The groups may also be bound, like in a match arm, for an expression
+/// whose value is then returned in the option. A mismatch between the
+/// bindings and the groups of the pattern is reported clearly:
+/// ```
+/// # use lazy_regex::*;
+/// let version = regex_captures!(
+///     r#"(\w+)-([0-9.]+)"#,
+///     "This is lazy_regex-2.0!" => (_, name, version) => format!("{name} v{version}"),
+/// );
+/// assert_eq!(version.as_deref(), Some("lazy_regex v2.0"));
+/// ```
+///
+/// Without the expression, the value is the tuple of the bound names,
+/// or the name when there's only one:
+/// ```
+/// # use lazy_regex::*;
+/// let version = regex_captures!(r#"(\w+)-([0-9.]+)"#, "lazy_regex-2.0" => (_, _, version));
+/// assert_eq!(version, Some("2.0"));
+/// ```
+///
+/// When the text is a literal, as above, the groups are captured at
+/// compile time.
 #[proc_macro]
 pub fn regex_captures(input: TokenStream) -> TokenStream {
-    process_with_value(input, false, |regex_code, value| {
-        let statick = regex_code.statick();
+    captures(input, |source| RegexCode::from_pattern(source, false), |regex_code, value| {
+        if let Some(result) = regex_code.eval_captures(&value) {
+            return result;
+        }
         let n = regex_code.captures_len();
         let groups = (0..n).map(|i| {
             quote! {
                 caps.get(#i).map_or("", |c| c.as_str())
             }
         });
-        quote! {{
-            #statick;
+        regex_code.scoped(quote! {
             RE.captures(#value)
                 .map(|caps| (
                     #(#groups),*
                 ))
-        }}
+        })
     })
 }
 
@@ -232,9 +465,11 @@ pub fn regex_captures(input: TokenStream) -> TokenStream {
 ///
 /// Example:
 /// ```
+/// # use lazy_regex::*;
+/// let text = b"This is lazy_regex-2.0!".to_vec();
 /// let (whole, name, version) = bytes_regex_captures!(
 ///     r#"(\w+)-([0-9.]+)"#, // a literal regex
-///     b"This is lazy_regex-2.0!", // any expression
+///     &text, // any expression
 /// ).unwrap();
 /// assert_eq!(whole, b"lazy_regex-2.0");
 /// assert_eq!(name, b"lazy_regex");
@@ -242,8 +477,181 @@ pub fn regex_captures(input: TokenStream) -> TokenStream {
 /// ```
 #[proc_macro]
 pub fn bytes_regex_captures(input: TokenStream) -> TokenStream {
+    captures(input, |source| RegexCode::from_pattern(source, true), |regex_code, value| {
+        if let Some(result) = regex_code.eval_captures(&value) {
+            return result;
+        }
+        let n = regex_code.captures_len();
+        let groups = (0..n).map(|i| {
+            quote! {
+                caps.get(#i).map_or(&b""[..], |c| c.as_bytes())
+            }
+        });
+        regex_code.scoped(quote! {
+            RE.captures(#value)
+                .map(|caps| (
+                    #(#groups),*
+                ))
+        })
+    })
+}
+
+/// Return an iterator over all the matches of the regex in the second
+/// argument, including the overlapping ones: for every position, all
+/// the matches starting there, from the shortest to the longest.
+///
+/// The regex is built as a `lazy_regex::AutomataRegex`,
+/// whose overlapping search needs the regex-automata engine, and the
+/// iterator yields values of type `AutomataMatch<'_, str>`, or
+/// `AutomataMatch<'_, [u8]>` if the `B` flag is set.
+///
+/// Example:
+/// ```
+/// # use lazy_regex::*;
+/// let starts: Vec<usize> = regex_find_overlapping!("aba", "ababa")
+///     .map(|m| m.start())
+///     .collect();
+/// assert_eq!(starts, vec![0, 2]);
+/// let matches: Vec<&str> = regex_find_overlapping!("a|ab", "ab")
+///     .map(|m| m.as_str())
+///     .collect();
+/// assert_eq!(matches, vec!["a", "ab"]);
+/// ```
+#[proc_macro]
+pub fn regex_find_overlapping(input: TokenStream) -> TokenStream {
+    process_with_value(input, false, |regex_code, value| {
+        let statick = regex_code.automata_statick();
+        quote! {{
+            #statick;
+            RE.find_overlapping(#value)
+        }}
+    })
+}
+
+/// Return an iterator over all the matches of the regex in the
+/// `&[u8]` slice given as second argument, including the overlapping ones.
+///
+/// The iterator yields values of type `AutomataMatch<'_, [u8]>`.
+#[proc_macro]
+pub fn bytes_regex_find_overlapping(input: TokenStream) -> TokenStream {
     process_with_value(input, true, |regex_code, value| {
-        let statick = regex_code.statick();
+        let statick = regex_code.automata_statick();
+        quote! {{
+            #statick;
+            RE.find_overlapping(#value)
+        }}
+    })
+}
+
+/// Extract the last match of the regex in the second argument,
+/// as a `&str`, or a `&[u8]` if the `B` flag is set.
+///
+/// The text is searched backward, from its end, so there's no need
+/// to iterate over all the previous matches.
+///
+/// Example:
+/// ```
+/// # use lazy_regex::*;
+/// let version = regex_rfind!(r"\d+(\.\d+)*", "tools/1.2/lazy-regex-3.6.0.tar.gz");
+/// assert_eq!(version, Some("3.6.0"));
+/// ```
+///
+/// The backward search finds where the last match starts, then the match
+/// is the one a search from there gives. When matches overlap, it's not
+/// always the last one `find_iter` would give:
+/// ```
+/// # use lazy_regex::*;
+/// assert_eq!(regex_rfind!(r"\d{2}", "123"), Some("23"));
+/// assert_eq!(regex!(r"\d{2}").find_iter("123").last().unwrap().as_str(), "12");
+/// ```
+///
+/// If the pattern has a Unicode word boundary and the backward search
+/// meets a non ASCII char, the text is searched forward instead, and the
+/// last match of `find_iter` is returned.
+///
+/// The regex is built with the regex-automata crate, so this macro isn't
+/// available with the `lite` feature.
+#[proc_macro]
+pub fn regex_rfind(input: TokenStream) -> TokenStream {
+    process_with_value(input, false, |regex_code, value| {
+        let statick = regex_code.automata_statick();
+        let as_method = match regex_code.regex {
+            RegexInstance::Regex(..) => quote!(as_str),
+            RegexInstance::Bytes(..) => quote!(as_bytes),
+        };
+        quote! {{
+            #statick;
+            RE.rfind(#value).map(|mat| mat. #as_method ())
+        }}
+    })
+}
+
+/// Extract the last match of the regex in the
+/// second argument as a `&[u8]`
+///
+/// The slice is searched backward, from its end, as
+/// with [`regex_rfind!`].
+///
+/// Example:
+/// ```
+/// # use lazy_regex::*;
+/// let word = bytes_regex_rfind!(r"\b\w+\b", b"The fox jumps.");
+/// assert_eq!(word, Some("jumps".as_bytes()));
+/// ```
+#[proc_macro]
+pub fn bytes_regex_rfind(input: TokenStream) -> TokenStream {
+    process_with_value(input, true, |regex_code, value| {
+        let statick = regex_code.automata_statick();
+        quote! {{
+            #statick;
+            RE.rfind(#value).map(|mat| mat.as_bytes())
+        }}
+    })
+}
+
+/// Extract the groups captured by the last match, as a tuple of &str.
+///
+/// This works like [`regex_captures!`] but the text is searched
+/// backward, from its end, for the match [`regex_rfind!`] would give.
+///
+/// Example:
+/// ```
+/// # use lazy_regex::*;
+/// let (_, hour, minute) = regex_rcaptures!(
+///     r"(\d{2}):(\d{2})",
+///     "started at 10:42, ended at 11:07",
+/// ).unwrap();
+/// assert_eq!(hour, "11");
+/// assert_eq!(minute, "07");
+/// ```
+#[proc_macro]
+pub fn regex_rcaptures(input: TokenStream) -> TokenStream {
+    process_with_value(input, false, |regex_code, value| {
+        let statick = regex_code.automata_statick();
+        let n = regex_code.captures_len();
+        let groups = (0..n).map(|i| {
+            quote! {
+                caps.get(#i).map_or("", |c| c.as_str())
+            }
+        });
+        quote! {{
+            #statick;
+            RE.rcaptures(#value)
+                .map(|caps| (
+                    #(#groups),*
+                ))
+        }}
+    })
+}
+
+/// Extract the groups captured by the last match, as a tuple of &[u8].
+///
+/// This works like [`bytes_regex_captures!`] but the slice is searched
+/// backward, from its end, for the match [`bytes_regex_rfind!`] would give.
+#[proc_macro]
+pub fn bytes_regex_rcaptures(input: TokenStream) -> TokenStream {
+    process_with_value(input, true, |regex_code, value| {
+        let statick = regex_code.automata_statick();
         let n = regex_code.captures_len();
         let groups = (0..n).map(|i| {
             quote! {
@@ -252,7 +660,7 @@ pub fn bytes_regex_captures(input: TokenStream) -> TokenStream {
         });
         quote! {{
             #statick;
-            RE.captures(#value)
+            RE.rcaptures(#value)
                 .map(|caps| (
                     #(#groups),*
                 ))
@@ -265,6 +673,7 @@ pub fn bytes_regex_captures(input: TokenStream) -> TokenStream {
 ///
 /// Example (adapted from the regex crate):
 /// ```
+/// # use lazy_regex::*;
 /// let hay = "'Citizen Kane' (1941), 'The Wizard of Oz' (1939), 'M' (1931).";
 /// let mut movies = vec![];
 /// let iter = regex_captures_iter!(r"'([^']+)'\s+\(([0-9]{4})\)", hay);
@@ -301,42 +710,50 @@ pub fn bytes_regex_captures_iter(input: TokenStream) -> TokenStream {
 }
 
 /// common implementation of `regex_replace` and `regex_replace_all`
-fn replacen(input: TokenStream, limit: usize) -> TokenStream {
+fn replacen(input: TokenStream, limit: usize, lite: bool) -> TokenStream {
     let parsed = parse_macro_input!(input as ReplaceArgs);
     let ReplaceArgs { regex_str, value, replacer } = parsed;
-    let regex_code = match RegexCode::from_lit_str(regex_str, false) {
+    let regex_code = if lite {
+        RegexCode::from_pattern_lite(regex_str)
+    } else {
+        RegexCode::from_pattern(regex_str, false)
+    };
+    let regex_code = match regex_code {
         Ok(r) => r,
         Err(e) => {
             return e.to_compile_error().into();
         }
     };
-    let statick = regex_code.statick();
+    let captures_type = regex_code.captures_type();
     let stream = match replacer {
         MaybeFun::Fun(fun) => {
+            let (or1, inputs, or2) = (&fun.or1_token, &fun.inputs, &fun.or2_token);
+            let params = quote!(#or1 #inputs #or2);
+            if let Some(e) = regex_code.check_arity(fun.inputs.len(), "closure argument", params) {
+                return e.to_compile_error().into();
+            }
             let n = regex_code.captures_len();
             let groups = (0..n).map(|i| {
                 quote! {
                     caps.get(#i).map_or("", |c| c.as_str())
                 }
             });
-            quote! {{
-                #statick;
+            regex_code.scoped(quote! {
                 RE.replacen(
                     #value,
                     #limit,
-                    |caps: &lazy_regex::Captures<'_>| {
+                    |caps: &#captures_type| {
                         let mut fun = #fun;
                         fun(
                             #(#groups),*
                         )
                     })
-            }}
+            })
         }
         MaybeFun::Expr(expr) => {
-            quote! {{
-                #statick;
+            regex_code.scoped(quote! {
                 RE.replacen(#value, #limit, #expr)
-            }}
+            })
         }
     };
     stream.into()
@@ -346,39 +763,42 @@ fn replacen(input: TokenStream, limit: usize) -> TokenStream {
 fn bytes_replacen(input: TokenStream, limit: usize) -> TokenStream {
     let parsed = parse_macro_input!(input as ReplaceArgs);
     let ReplaceArgs { regex_str, value, replacer } = parsed;
-    let regex_code = match RegexCode::from_lit_str(regex_str, true) {
+    let regex_code = match RegexCode::from_pattern(regex_str, true) {
         Ok(r) => r,
         Err(e) => {
             return e.to_compile_error().into();
         }
     };
-    let statick = regex_code.statick();
+    let captures_type = regex_code.captures_type();
     let stream = match replacer {
         MaybeFun::Fun(fun) => {
+            let (or1, inputs, or2) = (&fun.or1_token, &fun.inputs, &fun.or2_token);
+            let params = quote!(#or1 #inputs #or2);
+            if let Some(e) = regex_code.check_arity(fun.inputs.len(), "closure argument", params) {
+                return e.to_compile_error().into();
+            }
             let n = regex_code.captures_len();
             let groups = (0..n).map(|i| {
                 quote! {
                     caps.get(#i).map_or(&b""[..], |c| c.as_bytes())
                 }
             });
-            quote! {{
-                #statick;
+            regex_code.scoped(quote! {
                 RE.replacen(
                     #value,
                     #limit,
-                    |caps: &lazy_regex::regex::bytes::Captures<'_>| {
+                    |caps: &#captures_type| {
                         let mut fun = #fun;
                         fun(
                             #(#groups),*
                         )
                     })
-            }}
+            })
         }
         MaybeFun::Expr(expr) => {
-            quote! {{
-                #statick;
+            regex_code.scoped(quote! {
                 RE.replacen(#value, #limit, #expr)
-            }}
+            })
         }
     };
     stream.into()
@@ -394,6 +814,7 @@ fn bytes_replacen(input: TokenStream, limit: usize) -> TokenStream {
 ///
 /// Example:
 /// ```
+/// # use lazy_regex::*;
 /// let text = "Fuu fuuu";
 /// let text = regex_replace!(
 ///     "f(u*)"i,
@@ -404,7 +825,7 @@ fn bytes_replacen(input: TokenStream, limit: usize) -> TokenStream {
 /// ```
 #[proc_macro]
 pub fn regex_replace(input: TokenStream) -> TokenStream {
-    replacen(input, 1)
+    replacen(input, 1, false)
 }
 
 /// Replaces the leftmost match in the second argument
@@ -417,6 +838,7 @@ pub fn regex_replace(input: TokenStream) -> TokenStream {
 ///
 /// Example:
 /// ```
+/// # use lazy_regex::*;
 /// println!("{:?}", "ck ck".as_bytes());
 /// let text = b"Fuu fuuu";
 /// let text = bytes_regex_replace!(
@@ -441,6 +863,7 @@ pub fn bytes_regex_replace(input: TokenStream) -> TokenStream {
 ///
 /// Example:
 /// ```
+/// # use lazy_regex::*;
 /// let text = "Foo fuu";
 /// let text = regex_replace_all!(
 ///     r#"\bf(?P<suffix>\w+)"#i,
@@ -451,7 +874,7 @@ pub fn bytes_regex_replace(input: TokenStream) -> TokenStream {
 /// ```
 #[proc_macro]
 pub fn regex_replace_all(input: TokenStream) -> TokenStream {
-    replacen(input, 0)
+    replacen(input, 0, false)
 }
 
 /// Replaces all non-overlapping matches in the second argument
@@ -464,6 +887,7 @@ pub fn regex_replace_all(input: TokenStream) -> TokenStream {
 ///
 /// Example:
 /// ```
+/// # use lazy_regex::*;
 /// let text = b"Foo fuu";
 /// let text = bytes_regex_replace_all!(
 ///     r#"\bf(?P<suffix>\w+)"#i,
@@ -477,6 +901,93 @@ pub fn bytes_regex_replace_all(input: TokenStream) -> TokenStream {
     bytes_replacen(input, 0)
 }
 
+/// common implementation of the `regex_remove` macros
+fn remove(input: TokenStream, as_bytes: bool, all: bool) -> TokenStream {
+    process_with_value(input, as_bytes, |regex_code, value| {
+        if let Some(statick) = regex_code.literal_statick().filter(|_| all) {
+            return quote! {{
+                #statick;
+                RE.remove_all_matches(#value)
+            }};
+        }
+        if regex_code.is_automata() {
+            let method = if all {
+                quote!(remove_all_matches)
+            } else {
+                quote!(remove_match)
+            };
+            return regex_code.scoped(quote! {
+                RE. #method (#value)
+            });
+        }
+        let fun = match (&regex_code.regex, all) {
+            (RegexInstance::Regex(..), false) => quote!(remove_match),
+            (RegexInstance::Regex(..), true) => quote!(remove_all_matches),
+            (RegexInstance::Bytes(..), false) => quote!(bytes_remove_match),
+            (RegexInstance::Bytes(..), true) => quote!(bytes_remove_all_matches),
+        };
+        regex_code.scoped(quote! {
+            lazy_regex:: #fun (&RE, #value)
+        })
+    })
+}
+
+/// Remove the first match of a regex from the text, returning a borrowed slice when possible
+///
+/// Example:
+/// ```
+/// # use lazy_regex::*;
+/// let name = regex_remove!(
+///     r"-[0-9]+(\.[0-9]+)*$",
+///      "lazy-regex-3.5.2",
+/// );
+/// assert_eq!(name, "lazy-regex");
+/// assert!(matches!(name, std::borrow::Cow::Borrowed(_)));
+/// ```
+#[proc_macro]
+pub fn regex_remove(input: TokenStream) -> TokenStream {
+    remove(input, false, false)
+}
+
+/// Remove the first match of a regex from the `&[u8]` slice,
+/// returning a borrowed slice when possible
+#[proc_macro]
+pub fn bytes_regex_remove(input: TokenStream) -> TokenStream {
+    remove(input, true, false)
+}
+
+/// Remove all matches of a regex from the text
+///
+/// Example:
+/// ```
+/// # use lazy_regex::*;
+/// assert_eq!(
+///     regex_remove_all!(r"\s+", "    ab  c    d  e    "),
+///     "abcde"
+/// );
+/// ```
+///
+/// Whenever possible, no new string is allocated and a borrowed slice is returned, even when several matches are removed
+/// (if they're all either at the start or end of the text).
+///
+/// ```
+/// # use lazy_regex::*;
+/// let input = "154681string63731";
+/// let output = regex_remove_all!(r"\d", input);
+/// assert_eq!(output, "string");
+/// assert!(matches!(output, std::borrow::Cow::Borrowed("string")));
+/// ```
+#[proc_macro]
+pub fn regex_remove_all(input: TokenStream) -> TokenStream {
+    remove(input, false, true)
+}
+
+/// Remove all matches of a regex from the `&[u8]` slice
+#[proc_macro]
+pub fn bytes_regex_remove_all(input: TokenStream) -> TokenStream {
+    remove(input, true, true)
+}
+
 /// Return an `Option<T>`, with T being the type returned by the block or expression
 /// given as third argument.
 ///
@@ -484,6 +995,7 @@ pub fn bytes_regex_replace_all(input: TokenStream) -> TokenStream {
 /// Return None if the regex doesn't match.
 ///
 /// ```
+/// # use lazy_regex::*;
 ///  let grey = regex_if!(r#"^gr(a|e)y\((?<level>\d{1,2})\)$"#, "grey(22)", {
 ///      level.parse().unwrap()
 ///  });
@@ -496,7 +1008,7 @@ pub fn regex_if(input: TokenStream) -> TokenStream {
         value,
         then,
     } = parse_macro_input!(input as RexIfArgs);
-    let regex_code = match RegexCode::from_lit_str(regex_str, false) {
+    let regex_code = match RegexCode::from_pattern(regex_str, false) {
         Ok(r) => r,
         Err(e) => {
             return e.to_compile_error().into();
@@ -528,7 +1040,7 @@ pub fn bytes_regex_if(input: TokenStream) -> TokenStream {
         value,
         then,
     } = parse_macro_input!(input as RexIfArgs);
-    let regex_code = match RegexCode::from_lit_str(regex_str, true) {
+    let regex_code = match RegexCode::from_pattern(regex_str, true) {
         Ok(r) => r,
         Err(e) => {
             return e.to_compile_error().into();
@@ -559,8 +1071,12 @@ pub fn bytes_regex_if(input: TokenStream) -> TokenStream {
 /// computation.
 /// If no regex matches, return `None`.
 ///
+/// Flags given between the value and the arms, eg `F`, apply to all arms.
+///
 /// Example:
 /// ```
+/// # use lazy_regex::*;
+/// # fn main() -> Result<(), std::num::ParseIntError> {
 /// #[derive(Debug, PartialEq)]
 /// enum Color {
 ///     Grey(u8),
@@ -581,17 +1097,19 @@ pub fn bytes_regex_if(input: TokenStream) -> TokenStream {
 ///     ),
 /// );
 /// assert_eq!(color, Some(Color::Rgb(1, 2, 3)));
-///
+/// # Ok(())
+/// # }
 /// ```
 #[proc_macro]
 pub fn regex_switch(input: TokenStream) -> TokenStream {
     let RexSwitchArgs {
         value,
+        flags,
         arms,
     } = parse_macro_input!(input as RexSwitchArgs);
     let mut q_arms = Vec::new();
     for RexSwitchArmArgs { regex_str, then } in arms {
-        let regex_code = match RegexCode::from_lit_str(regex_str, false) {
+        let regex_code = match RegexCode::from_pattern_with_flags(regex_str, false, flags.as_ref()) {
             Ok(r) => r,
             Err(e) => {
                 return e.to_compile_error().into();
@@ -629,39 +1147,34 @@ pub fn regex_switch(input: TokenStream) -> TokenStream {
 /// computation.
 /// If no regex matches, return `None`.
 ///
+/// Flags given between the value and the arms, eg `F`, apply to all arms.
+///
 /// Example:
 /// ```
+/// # use lazy_regex::*;
 /// #[derive(Debug, PartialEq)]
-/// enum Color {
-///     Grey(u8),
-///     Pink,
-///     Rgb(u8, u8, u8),
+/// enum Image {
+///     Gif(u8),
+///     Jpeg,
 /// }
 ///
-/// let input = "rgb(1, 2, 3)";
-/// let color = regex_switch!(input,
-///     r#"^gr(a|e)y\((?<level>\d{1,2})\)$"#i => {
-///         Color::Grey(level.parse()?)
-///     }
-///     "^pink"i => Color::Pink,
-///     r#"^rgb\((?<r>\d+),\s*(?<g>\d+),\s*(?<b>\d+),?\)$"#i => Color::Rgb (
-///         r.parse()?,
-///         g.parse()?,
-///         b.parse()?,
-///     ),
+/// let header = b"GIF87a...";
+/// let image = bytes_regex_switch!(header,
+///     "^GIF8(?<version>[79])a" => Image::Gif(version[0] - b'0'),
+///     r"(?-u)^\xFF\xD8" => Image::Jpeg,
 /// );
-/// assert_eq!(color, Some(Color::Rgb(1, 2, 3)));
-///
+/// assert_eq!(image, Some(Image::Gif(7)));
 /// ```
 #[proc_macro]
 pub fn bytes_regex_switch(input: TokenStream) -> TokenStream {
     let RexSwitchArgs {
         value,
+        flags,
         arms,
     } = parse_macro_input!(input as RexSwitchArgs);
     let mut q_arms = Vec::new();
     for RexSwitchArmArgs { regex_str, then } in arms {
-        let regex_code = match RegexCode::from_lit_str(regex_str, true) {
+        let regex_code = match RegexCode::from_pattern_with_flags(regex_str, true, flags.as_ref()) {
             Ok(r) => r,
             Err(e) => {
                 return e.to_compile_error().into();
@@ -692,3 +1205,403 @@ pub fn bytes_regex_switch(input: TokenStream) -> TokenStream {
         }
     }}.into()
 }
+
+/// Define a set of named rules, whose patterns may refer to other
+/// rules with `(?&name)`.
+///
+/// References are expanded at compile time (cycles are detected), then
+/// each rule is exported as a lazy static regex named after the rule in
+/// uppercase, along with a `&'static str` constant of its expanded pattern.
+///
+/// Example:
+/// ```
+/// # use lazy_regex::*;
+/// regex_grammar! {
+///     octet = r"25[0-5]|2[0-4]\d|1?\d?\d";
+///     pub ipv4 = r"^(?&octet)(\.(?&octet)){3}$";
+/// }
+/// assert!(IPV4.is_match("192.168.0.1"));
+/// assert!(!IPV4.is_match("192.168.0.256"));
+/// assert!(IPV4_PATTERN.starts_with("^(?-imsxU:25[0-5]"));
+/// ```
+///
+/// A referenced rule is matched with its own flags only: the flags
+/// it doesn't have are turned off in its expansion, so that the ones
+/// of the referencing rule don't apply to it.
+///
+/// A reference to an unknown rule, a cycle of references, or a rule
+/// with a named group referenced twice in a pattern, are errors:
+/// ```compile_fail
+/// # use lazy_regex::*;
+/// regex_grammar! {
+///     // error: unknown rule "digits"
+///     number = r"(?&digits)(\.(?&digits))?";
+/// }
+/// ```
+/// ```compile_fail
+/// # use lazy_regex::*;
+/// regex_grammar! {
+///     // error: cyclic rule reference: list -> item -> list
+///     item = r"\w+|\[(?&list)\]";
+///     list = r"(?&item)(,(?&item))*";
+/// }
+/// ```
+/// ```compile_fail
+/// # use lazy_regex::*;
+/// regex_grammar! {
+///     word = r"(?P<w>\w+)";
+///     // error: the group name "w" is duplicated in the expansion of `pair`
+///     pair = r"(?&word)=(?&word)";
+/// }
+/// ```
+#[proc_macro]
+pub fn regex_grammar(input: TokenStream) -> TokenStream {
+    let NamedPatterns { items } = parse_macro_input!(input as NamedPatterns);
+    let patterns = match (Grammar { rules: &items }).expand() {
+        Ok(patterns) => patterns,
+        Err(e) => {
+            return e.to_compile_error().into();
+        }
+    };
+    let mut q_items = Vec::new();
+    for (item, pattern) in items.into_iter().zip(patterns) {
+        let NamedPattern { attrs, vis, name, regex_str } = item;
+        let source = PatternSource {
+            value: pattern.clone(),
+            ..regex_str
+        };
+        let regex_code = match RegexCode::from_pattern(source, false) {
+            Ok(r) => r,
+            Err(e) => {
+                return e.to_compile_error().into();
+            }
+        };
+        let build = regex_code.lazy();
+        let regex_type = regex_code.regex_type();
+        let upper = name.to_string().to_uppercase();
+        let static_name = syn::Ident::new(&upper, name.span());
+        let const_name = syn::Ident::new(&format!("{upper}_PATTERN"), name.span());
+        let static_doc = format!("Regex of the `{name}` rule: `{pattern}`");
+        let const_doc = format!("Expanded pattern of the `{name}` rule");
+        q_items.push(quote! {
+            #(#attrs)*
+            #[doc = #static_doc]
+            #[allow(dead_code)]
+            #vis static #static_name: lazy_regex::Lazy<lazy_regex:: #regex_type> = #build;
+            #[doc = #const_doc]
+            #[allow(dead_code)]
+            #vis const #const_name: &str = #pattern;
+        });
+    }
+    quote! {
+        #(#q_items)*
+    }.into()
+}
+
+/// Declare several lazy static regexes, whose type (`Regex`, `BytesRegex`,
+/// etc.) depends on the flags.
+///
+/// Each static comes with a module of the same name holding the indices
+/// of its named groups, as uppercase constants, along with the
+/// `CAPTURES_LEN` and `GROUP_NAMES` constants.
+///
+/// Example:
+/// ```
+/// # use lazy_regex::*;
+/// regexes! {
+///     /// Dates in the ISO 8601 format
+///     pub DATE = r"(?<year>\d{4})-(?<month>\d{2})-(?<day>\d{2})";
+///     pub(crate) TAG = r"<(?<name>\w+)>"B;
+/// }
+/// let caps = DATE.captures("2026-10-18").unwrap();
+/// assert_eq!(&caps[DATE::MONTH], "10");
+/// assert!(TAG.is_match(b"<br>"));
+/// ```
+#[proc_macro]
+pub fn regexes(input: TokenStream) -> TokenStream {
+    let NamedPatterns { items } = parse_macro_input!(input as NamedPatterns);
+    let mut q_items = Vec::new();
+    for NamedPattern { attrs, vis, name, regex_str } in items {
+        let regex_code = match RegexCode::from_pattern(regex_str, false) {
+            Ok(r) => r,
+            Err(e) => {
+                return e.to_compile_error().into();
+            }
+        };
+        let build = regex_code.lazy();
+        let regex_type = regex_code.regex_type();
+        let pattern = regex_code.verbose_pattern.as_ref().unwrap_or(&regex_code.pattern);
+        let pattern_doc = format!("```text\n{pattern}\n```");
+        let mut groups = Vec::new();
+        for (idx, group_name) in regex_code.named_groups() {
+            let const_name = group_name.to_uppercase();
+            if const_name == "CAPTURES_LEN" || const_name == "GROUP_NAMES" {
+                return syn::Error::new_spanned(
+                    &name,
+                    format!("the `{group_name}` group would conflict with the {const_name} constant"),
+                ).to_compile_error().into();
+            }
+            let const_name = syn::Ident::new(&const_name, name.span());
+            let doc = format!("Index of the `{group_name}` group");
+            groups.push(quote! {
+                #[doc = #doc]
+                pub const #const_name: usize = #idx;
+            });
+        }
+        let captures_len = regex_code.captures_len();
+        let group_names = regex_code.group_names().into_iter().map(|name| match name {
+            Some(name) => quote!(Some(#name)),
+            None => quote!(None),
+        });
+        let mod_doc = format!("Group layout of the `{name}` regex");
+        q_items.push(quote! {
+            #(#attrs)*
+            #[doc = #pattern_doc]
+            #vis static #name: lazy_regex::Lazy<lazy_regex:: #regex_type> = #build;
+            #[doc = #mod_doc]
+            #[allow(non_snake_case, dead_code)]
+            #vis mod #name {
+                /// Number of groups, including the implicit group of the whole match
+                pub const CAPTURES_LEN: usize = #captures_len;
+                /// Names of the groups, `None` for the unnamed ones
+                pub const GROUP_NAMES: [Option<&str>; #captures_len] = [#(#group_names),*];
+                #(#groups)*
+            }
+        });
+    }
+    quote! {
+        #(#q_items)*
+    }.into()
+}
+
+/// Return a lazy static `DfaRegex` whose DFAs are built at compile
+/// time and embedded in the binary, so that the first use only has
+/// to deserialize them instead of compiling the pattern.
+///
+/// When the DFAs can't be built, because they'd be bigger than the
+/// `dfa_size_limit` option (1 MiB by default) or because the pattern
+/// has a Unicode word boundary, the regex is compiled at first use,
+/// as with `regex!`.
+///
+/// Example:
+/// ```
+/// # use lazy_regex::*;
+/// let r: &DfaRegex = dfa_regex!(r"^\d{4}-\d{2}-\d{2}$");
+/// assert!(r.is_precompiled());
+/// assert!(r.is_match("2026-10-18"));
+/// assert_eq!(r.find("2026-10-18").unwrap().as_str(), "2026-10-18");
+/// ```
+#[proc_macro]
+pub fn dfa_regex(input: TokenStream) -> TokenStream {
+    process(input, false, |regex_code| {
+        let build = match regex_code.dfa_build() {
+            Ok(build) => build,
+            Err(e) => return e.to_compile_error(),
+        };
+        let regex_type = regex_code.dfa_regex_type();
+        quote! {{
+            static RE: lazy_regex::Lazy<lazy_regex:: #regex_type > = #build;
+            &RE
+        }}
+    })
+}
+
+/// Test whether an expression matches a lazy static `DfaRegex`, whose
+/// DFAs are built at compile time (see [`dfa_regex!`])
+///
+/// Example:
+/// ```
+/// # use lazy_regex::*;
+/// assert!(dfa_regex_is_match!("[ab]+", "car"));
+/// ```
+#[proc_macro]
+pub fn dfa_regex_is_match(input: TokenStream) -> TokenStream {
+    process_with_value(input, false, |regex_code, value| {
+        let build = match regex_code.dfa_build() {
+            Ok(build) => build,
+            Err(e) => return e.to_compile_error(),
+        };
+        let regex_type = regex_code.dfa_regex_type();
+        quote! {{
+            static RE: lazy_regex::Lazy<lazy_regex:: #regex_type > = #build;
+            RE.is_match(#value)
+        }}
+    })
+}
+
+/// Return a lazy static `regex_lite::Regex` checked at compilation
+/// time and built at first use.
+///
+/// The lite macros are available with the `lite-macros` feature, which,
+/// contrary to the `lite` feature, doesn't change the engine of the other
+/// macros: they let you choose regex-lite per call site.
+///
+/// Example:
+/// ```
+/// # use lazy_regex::*;
+/// let r: &LiteRegex = lite_regex!("^ab+$"i);
+/// assert!(r.is_match("aBb"));
+/// ```
+#[proc_macro]
+pub fn lite_regex(input: TokenStream) -> TokenStream {
+    process_lite(input, |regex_code| regex_code.lazy_static())
+}
+
+/// Return an instance of `once_cell::sync::Lazy<regex_lite::Regex>`
+/// that you can use in a public static declaration.
+///
+/// Example:
+/// ```
+/// # use lazy_regex::*;
+/// pub static GLOBAL_REX: Lazy<LiteRegex> = lite_lazy_regex!("^ab+$"i);
+/// ```
+#[proc_macro]
+pub fn lite_lazy_regex(input: TokenStream) -> TokenStream {
+    process_lite(input, |regex_code| regex_code.lazy())
+}
+
+/// Test whether an expression matches a lazy static
+/// `regex_lite::Regex` (the regex is checked at compile time)
+///
+/// Example:
+/// ```
+/// # use lazy_regex::*;
+/// assert!(lite_regex_is_match!("[ab]+", "car"));
+/// ```
+#[proc_macro]
+pub fn lite_regex_is_match(input: TokenStream) -> TokenStream {
+    process_with_value_lite(input, |regex_code, value| {
+        regex_code.scoped(quote! {
+            RE.is_match(#value)
+        })
+    })
+}
+
+/// Extract the leftmost match of a `regex_lite::Regex`, as `regex_find!`
+///
+/// Example:
+/// ```
+/// # use lazy_regex::*;
+/// let f_word = lite_regex_find!(r"\bf\w+\b", "The fox jumps.");
+/// assert_eq!(f_word, Some("fox"));
+/// ```
+#[proc_macro]
+pub fn lite_regex_find(input: TokenStream) -> TokenStream {
+    process_with_value_lite(input, |regex_code, value| {
+        regex_code.scoped(quote! {
+            RE.find(#value).map(|mat| mat.as_str())
+        })
+    })
+}
+
+/// Extract captured groups of a `regex_lite::Regex` as a tuple
+/// of &str, as `regex_captures!`
+///
+/// Example:
+/// ```
+/// # use lazy_regex::*;
+/// let (whole, name, version) = lite_regex_captures!(
+///     r"(\w+)-([0-9.]+)",
+///     "This is lazy_regex-2.0!",
+/// ).unwrap();
+/// assert_eq!(version, "2.0");
+/// ```
+#[proc_macro]
+pub fn lite_regex_captures(input: TokenStream) -> TokenStream {
+    captures(input, RegexCode::from_pattern_lite, |regex_code, value| {
+        let n = regex_code.captures_len();
+        let groups = (0..n).map(|i| {
+            quote! {
+                caps.get(#i).map_or("", |c| c.as_str())
+            }
+        });
+        regex_code.scoped(quote! {
+            RE.captures(#value)
+                .map(|caps| (
+                    #(#groups),*
+                ))
+        })
+    })
+}
+
+/// Return an iterator over the captures of the successive
+/// non-overlapping matches of a `regex_lite::Regex`
+#[proc_macro]
+pub fn lite_regex_captures_iter(input: TokenStream) -> TokenStream {
+    process_with_value_lite(input, |regex_code, value| {
+        let statick = regex_code.statick();
+        quote! {{
+            #statick;
+            RE.captures_iter(#value)
+        }}
+    })
+}
+
+/// Replace the leftmost match of a `regex_lite::Regex`, as `regex_replace!`
+///
+/// Example:
+/// ```
+/// # use lazy_regex::*;
+/// let text = lite_regex_replace!("f(u*)"i, "Fuu fuuu", |_, suffix: &str| format!("F{}", suffix.len()));
+/// assert_eq!(text, "F2 fuuu");
+/// ```
+#[proc_macro]
+pub fn lite_regex_replace(input: TokenStream) -> TokenStream {
+    replacen(input, 1, true)
+}
+
+/// Replace all matches of a `regex_lite::Regex`, as `regex_replace_all!`
+///
+/// Example:
+/// ```
+/// # use lazy_regex::*;
+/// let text = lite_regex_replace_all!(r"\d+", "a1 b22", "#");
+/// assert_eq!(text, "a# b#");
+/// ```
+#[proc_macro]
+pub fn lite_regex_replace_all(input: TokenStream) -> TokenStream {
+    replacen(input, 0, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Return the error of the bindings given to `regex_captures!`,
+    /// with the position of the tuple of the bindings
+    fn captures_error(input: &str) -> (syn::Error, usize) {
+        let parsed: RexCapturesArgs = syn::parse_str(input).unwrap();
+        let (bindings, _) = parsed.arm.unwrap();
+        let regex_code = RegexCode::from_pattern(parsed.regex_str, false).unwrap();
+        let e = bindings_error(&regex_code, &bindings).unwrap();
+        (e, input.find("=> (").unwrap() + 3)
+    }
+
+    #[test]
+    fn test_bindings_error() {
+        let input = r#"r"(?<name>\w+)-([0-9.]+)", "lazy_regex-2.0" => (name, version) => version"#;
+        let (e, column) = captures_error(input);
+        assert_eq!(
+            e.to_string(),
+            "pattern has 3 groups (0: whole, 1: name, 2: -) but 2 bindings were given",
+        );
+        // the error points at the tuple of the bindings
+        let span = e.span();
+        assert_eq!(span.start().column, column);
+        assert_eq!(span.end().column, column + "(name, version)".len());
+
+        let input = r#""(a)(b)(c)", text => (_, _, _, c, d)"#;
+        let (e, column) = captures_error(input);
+        assert_eq!(
+            e.to_string(),
+            "pattern has 4 groups (0: whole, 1: -, 2: -, 3: -) but 5 bindings were given",
+        );
+        assert_eq!(e.span().start().column, column);
+
+        let input = r#""(a)(b)", text => (whole, ..)"#;
+        let parsed: RexCapturesArgs = syn::parse_str(input).unwrap();
+        let (bindings, _) = parsed.arm.unwrap();
+        let regex_code = RegexCode::from_pattern(parsed.regex_str, false).unwrap();
+        assert!(bindings_error(&regex_code, &bindings).is_none());
+    }
+}
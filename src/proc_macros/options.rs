@@ -0,0 +1,234 @@
+use {
+    crate::regex_code::RegexFlags,
+    proc_macro2::TokenStream as TokenStream2,
+    quote::quote,
+    syn::{
+        parse::{
+            Parse,
+            ParseStream,
+        },
+        BinOp,
+        Expr,
+        ExprLit,
+        Ident,
+        Lit,
+        Token,
+    },
+};
+
+/// A builder option given after the pattern, either as a name
+/// (`crlf`) or as an assignment (`size_limit = 1 << 20`)
+pub(crate) struct RegexOption {
+    pub name: Ident,
+    pub value: Option<Expr>,
+}
+
+impl Parse for RegexOption {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let name = input.parse::<Ident>()?;
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse::<Expr>()?)
+        } else {
+            None
+        };
+        Ok(Self { name, value })
+    }
+}
+
+/// The builder options which aren't regex flags, with their values
+/// computed at compile time so that the check of the pattern is done
+/// with the same configuration as the runtime build
+#[derive(Default)]
+pub(crate) struct RegexOptions {
+    pub crlf: Option<bool>,
+    pub line_terminator: Option<u8>,
+    pub unicode: Option<bool>,
+    pub octal: Option<bool>,
+    pub nest_limit: Option<u32>,
+    pub size_limit: Option<usize>,
+    pub dfa_size_limit: Option<usize>,
+    /// whether the regex is wrapped in a `TypedRegex`
+    pub typed: bool,
+    /// the builder calls applying those options at runtime
+    pub calls: TokenStream2,
+}
+
+impl RegexOptions {
+    /// Read the options, setting the flags when the option is one of them
+    pub fn new(
+        options: &[RegexOption],
+        flags: &mut RegexFlags,
+        lite: bool,
+    ) -> syn::Result<Self> {
+        let mut o = Self::default();
+        for RegexOption { name, value } in options {
+            let flag = match name.to_string().as_str() {
+                "case_insensitive" => Some(&mut flags.case_insensitive),
+                "multi_line" => Some(&mut flags.multi_line),
+                "dot_matches_new_line" => Some(&mut flags.dot_matches_new_line),
+                "ignore_whitespace" => Some(&mut flags.ignore_whitespace),
+                "swap_greed" => Some(&mut flags.swap_greed),
+                _ => None,
+            };
+            if let Some(flag) = flag {
+                *flag = bool_value(name, value)?;
+                continue;
+            }
+            // regex-lite only knows about unicode in the pattern, and
+            // has no lazy DFA
+            if lite
+                && matches!(
+                    name.to_string().as_str(),
+                    "unicode" | "octal" | "line_terminator" | "dfa_size_limit",
+                )
+            {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("the `{name}` option isn't available with regex-lite"),
+                ));
+            }
+            let call = match name.to_string().as_str() {
+                "flags" => continue, // handled with the other flags
+                "typed" => {
+                    o.typed = bool_value(name, value)?;
+                    continue;
+                }
+
+                "crlf" => {
+                    let yes = bool_value(name, value)?;
+                    o.crlf = Some(yes);
+                    quote!(.crlf(#yes))
+                }
+                "unicode" => {
+                    let yes = bool_value(name, value)?;
+                    o.unicode = Some(yes);
+                    quote!(.unicode(#yes))
+                }
+                "octal" => {
+                    let yes = bool_value(name, value)?;
+                    o.octal = Some(yes);
+                    quote!(.octal(#yes))
+                }
+                "line_terminator" => {
+                    let (expr, byte) = int_value(name, value)?;
+                    o.line_terminator = Some(byte);
+                    quote!(.line_terminator(#expr))
+                }
+                "nest_limit" => {
+                    let (expr, limit) = int_value(name, value)?;
+                    o.nest_limit = Some(limit);
+                    quote!(.nest_limit(#expr))
+                }
+                "size_limit" => {
+                    let (expr, bytes) = int_value(name, value)?;
+                    o.size_limit = Some(bytes);
+                    quote!(.size_limit(#expr))
+                }
+                "dfa_size_limit" => {
+                    let (expr, bytes) = int_value(name, value)?;
+                    o.dfa_size_limit = Some(bytes);
+                    quote!(.dfa_size_limit(#expr))
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        format!("unknown regex option `{name}`"),
+                    ));
+                }
+            };
+            o.calls.extend(call);
+        }
+        Ok(o)
+    }
+
+    /// Return the values of the options set, eg `"crlf=true,size_limit=1048576"`,
+    /// for the key under which the regex is shared between call sites
+    pub fn key(&self) -> String {
+        let mut values = Vec::new();
+        let mut push = |name: &str, value: Option<String>| {
+            if let Some(value) = value {
+                values.push(format!("{name}={value}"));
+            }
+        };
+        push("crlf", self.crlf.map(|v| v.to_string()));
+        push("line_terminator", self.line_terminator.map(|v| v.to_string()));
+        push("unicode", self.unicode.map(|v| v.to_string()));
+        push("octal", self.octal.map(|v| v.to_string()));
+        push("nest_limit", self.nest_limit.map(|v| v.to_string()));
+        push("size_limit", self.size_limit.map(|v| v.to_string()));
+        push("dfa_size_limit", self.dfa_size_limit.map(|v| v.to_string()));
+        values.join(",")
+    }
+}
+
+/// Read the value of a boolean option, which is `true` when only
+/// the name of the option is given
+fn bool_value(name: &Ident, value: &Option<Expr>) -> syn::Result<bool> {
+    match value {
+        None => Ok(true),
+        Some(Expr::Lit(ExprLit { lit: Lit::Bool(b), .. })) => Ok(b.value),
+        Some(expr) => Err(syn::Error::new_spanned(
+            expr,
+            format!("the value of {name} must be true or false"),
+        )),
+    }
+}
+
+/// Read the value of an integer option, which must be a literal or an
+/// arithmetic expression of literals, returning both the expression and its value
+fn int_value<'e, T: TryFrom<u64>>(
+    name: &Ident,
+    value: &'e Option<Expr>,
+) -> syn::Result<(&'e Expr, T)> {
+    let Some(expr) = value else {
+        return Err(syn::Error::new(
+            name.span(),
+            format!("{name} needs a value, eg `{name} = 100`"),
+        ));
+    };
+    let n = eval_int(expr)?;
+    let n = T::try_from(n).map_err(|_| {
+        syn::Error::new_spanned(expr, format!("the value of {name} is too big"))
+    })?;
+    Ok((expr, n))
+}
+
+/// Compute the value of a constant integer expression made of
+/// literals, parentheses, and arithmetic or bitwise operators
+fn eval_int(expr: &Expr) -> syn::Result<u64> {
+    let overflow = || syn::Error::new_spanned(expr, "integer overflow");
+    match expr {
+        Expr::Lit(ExprLit { lit: Lit::Int(i), .. }) => i.base10_parse(),
+        Expr::Lit(ExprLit { lit: Lit::Byte(b), .. }) => Ok(b.value().into()),
+        Expr::Paren(paren) => eval_int(&paren.expr),
+        Expr::Group(group) => eval_int(&group.expr),
+        Expr::Binary(binary) => {
+            let a = eval_int(&binary.left)?;
+            let b = eval_int(&binary.right)?;
+            let n = match binary.op {
+                BinOp::Add(_) => a.checked_add(b),
+                BinOp::Sub(_) => a.checked_sub(b),
+                BinOp::Mul(_) => a.checked_mul(b),
+                BinOp::Div(_) => a.checked_div(b),
+                BinOp::Rem(_) => a.checked_rem(b),
+                BinOp::Shl(_) => u32::try_from(b).ok().and_then(|b| a.checked_shl(b)),
+                BinOp::Shr(_) => u32::try_from(b).ok().and_then(|b| a.checked_shr(b)),
+                BinOp::BitAnd(_) => Some(a & b),
+                BinOp::BitOr(_) => Some(a | b),
+                BinOp::BitXor(_) => Some(a ^ b),
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        binary.op,
+                        "unsupported operator in an option value",
+                    ));
+                }
+            };
+            n.ok_or_else(overflow)
+        }
+        _ => Err(syn::Error::new_spanned(
+            expr,
+            "expected an integer literal, or an arithmetic expression of literals, eg `1 << 20`",
+        )),
+    }
+}
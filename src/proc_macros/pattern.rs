@@ -0,0 +1,223 @@
+use {
+    proc_macro2::{
+        Literal,
+        TokenStream as TokenStream2,
+    },
+    quote::ToTokens,
+    std::path::PathBuf,
+    syn::{
+        parse::{
+            Parse,
+            ParseStream,
+        },
+        punctuated::Punctuated,
+        spanned::Spanned,
+        Lit,
+        LitByteStr,
+        LitStr,
+        Macro,
+        Token,
+    },
+};
+
+/// The pattern given to a macro: either a string or byte string
+/// literal, with the flags as suffix, or a `concat!` or `include_str!`
+/// call whose value is computed at compile time
+pub(crate) struct PatternSource {
+    pub value: String,
+    /// the literal (a `LitStr` or a `LitByteStr`), when the pattern
+    /// is given as one
+    pub lit: Option<Lit>,
+    /// the tokens of the literal or macro call, to which errors point
+    pub tokens: TokenStream2,
+    /// absolute paths of the files read by `include_str!`
+    pub included_files: Vec<String>,
+}
+
+impl Parse for PatternSource {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            let lit = input.parse::<LitStr>()?;
+            return Ok(Self {
+                value: lit.value(),
+                tokens: lit.to_token_stream(),
+                lit: Some(Lit::Str(lit)),
+                included_files: Vec::new(),
+            });
+        }
+        if input.peek(LitByteStr) {
+            let lit = input.parse::<LitByteStr>()?;
+            return Ok(Self {
+                value: bytes_pattern(&lit.value()),
+                tokens: lit.to_token_stream(),
+                lit: Some(Lit::ByteStr(lit)),
+                included_files: Vec::new(),
+            });
+        }
+        let mac = input.parse::<Macro>().map_err(|e| {
+            syn::Error::new(
+                e.span(),
+                "expected a string literal, or a `concat!` or `include_str!` call",
+            )
+        })?;
+        let mut pattern = Self {
+            value: String::new(),
+            lit: None,
+            tokens: mac.to_token_stream(),
+            included_files: Vec::new(),
+        };
+        pattern.push_macro(&mac)?;
+        Ok(pattern)
+    }
+}
+
+/// An argument of `concat!`: a literal or a nested macro call
+enum Fragment {
+    Lit(Lit),
+    Macro(Macro),
+}
+
+impl Parse for Fragment {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        if input.peek(Lit) {
+            Ok(Self::Lit(input.parse()?))
+        } else {
+            Ok(Self::Macro(input.parse()?))
+        }
+    }
+}
+
+/// Convert the value of a byte string literal into a pattern, the
+/// non ASCII bytes being escaped so that they match themselves
+/// rather than a char.
+///
+/// Outside of a class, a byte is written as `(?-u:\xFF)`. A class can't
+/// hold a group, so a byte in a class is written as `\xFF` and the whole
+/// class is wrapped in `(?-u:...)`, eg `(?-u:[\x80-\xFF])`: without it,
+/// `\xFF` would be the char U+00FF.
+fn bytes_pattern(bytes: &[u8]) -> String {
+    let mut pattern = String::with_capacity(bytes.len());
+    let mut class_depth = 0;
+    // where the outermost class starts in the pattern, and whether
+    // it holds a raw byte
+    let mut class_start = 0;
+    let mut class_has_byte = false;
+    let mut escaped = false;
+    for (i, &b) in bytes.iter().enumerate() {
+        if !b.is_ascii() {
+            if escaped {
+                pattern.pop(); // the escape is useless, the byte is escaped anyway
+            }
+            if class_depth > 0 {
+                pattern.push_str(&format!("\\x{b:02X}"));
+                class_has_byte = true;
+            } else {
+                pattern.push_str(&format!("(?-u:\\x{b:02X})"));
+            }
+            escaped = false;
+            continue;
+        }
+        if b == b'[' && class_depth == 0 && !escaped {
+            class_start = pattern.len();
+            class_has_byte = false;
+        }
+        pattern.push(b as char);
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match b {
+            b'\\' => escaped = true,
+            b'[' => class_depth += 1,
+            // a `]` just after the opening of a class is a literal
+            b']' if class_depth > 0 && !bytes[..i].ends_with(b"[") && !bytes[..i].ends_with(b"[^") => {
+                class_depth -= 1;
+                if class_depth == 0 && class_has_byte {
+                    pattern.insert_str(class_start, "(?-u:");
+                    pattern.push(')');
+                }
+            }
+            _ => {}
+        }
+    }
+    pattern
+}
+
+impl PatternSource {
+    /// Return the token of the literal, when the pattern is given as one
+    pub fn lit_token(&self) -> Option<Literal> {
+        match self.lit.as_ref()? {
+            Lit::Str(lit) => Some(lit.token()),
+            Lit::ByteStr(lit) => Some(lit.token()),
+            _ => None,
+        }
+    }
+    /// Append the value of the macro call to the pattern
+    fn push_macro(&mut self, mac: &Macro) -> syn::Result<()> {
+        let name = mac.path.segments.last().map(|s| s.ident.to_string());
+        match name.as_deref() {
+            Some("concat") => {
+                let fragments = mac.parse_body_with(Punctuated::<Fragment, Token![,]>::parse_terminated)?;
+                for fragment in fragments {
+                    match fragment {
+                        Fragment::Lit(lit) => self.push_lit(&lit)?,
+                        Fragment::Macro(mac) => self.push_macro(&mac)?,
+                    }
+                }
+            }
+            Some("include_str") => {
+                let mut path = Self {
+                    value: String::new(),
+                    lit: None,
+                    tokens: mac.to_token_stream(),
+                    included_files: Vec::new(),
+                };
+                match mac.parse_body::<Fragment>()? {
+                    Fragment::Lit(lit) => path.push_lit(&lit)?,
+                    Fragment::Macro(mac) => path.push_macro(&mac)?,
+                }
+                self.included_files.append(&mut path.included_files);
+                let dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+                let path: PathBuf = [dir, path.value].iter().collect();
+                let content = std::fs::read_to_string(&path).map_err(|e| {
+                    syn::Error::new(
+                        mac.tokens.span(),
+                        format!("couldn't read {}: {e}", path.display()),
+                    )
+                })?;
+                self.value.push_str(&content);
+                self.included_files.push(path.to_string_lossy().to_string());
+            }
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &mac.path,
+                    "only `concat!` and `include_str!` can be used to build a pattern",
+                ));
+            }
+        }
+        Ok(())
+    }
+    /// Append the value of a literal, as `concat!` would
+    fn push_lit(&mut self, lit: &Lit) -> syn::Result<()> {
+        if !lit.suffix().is_empty() {
+            return Err(syn::Error::new(
+                lit.span(),
+                "flags can't be given as suffix here, use the `flags` option",
+            ));
+        }
+        match lit {
+            Lit::Str(s) => self.value.push_str(&s.value()),
+            Lit::Char(c) => self.value.push(c.value()),
+            Lit::Int(i) => self.value.push_str(i.base10_digits()),
+            Lit::Float(f) => self.value.push_str(f.base10_digits()),
+            Lit::Bool(b) => self.value.push_str(if b.value { "true" } else { "false" }),
+            _ => {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "this literal can't be concatenated to a pattern",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
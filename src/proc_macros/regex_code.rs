@@ -1,15 +1,108 @@
 use {
+    crate::{
+        args::RegexArgs,
+        diagnostic::*,
+        pattern::PatternSource,
+        verbose::minimal_pattern,
+        options::{
+            RegexOption,
+            RegexOptions,
+        },
+    },
     proc_macro::TokenStream,
-    proc_macro2::TokenStream as TokenStream2,
+    proc_macro2::{
+        Span,
+        TokenStream as TokenStream2,
+    },
     quote::quote,
-    syn::LitStr,
+    std::{
+        collections::hash_map::DefaultHasher,
+        hash::{
+            Hash,
+            Hasher,
+        },
+    },
+    syn::{
+        Expr,
+        ExprLit,
+        Ident,
+        Lit,
+    },
 };
 
 /// The lazy static regex building code, which is produced and
 /// inserted by all lazy-regex macros
 pub(crate) struct RegexCode {
     pub build: TokenStream2,
+    /// the lazy static initializer of the regex built as an `AutomataRegex`
+    pub automata_build: TokenStream2,
     pub regex: RegexInstance,
+    /// the pattern used at runtime
+    pub pattern: String,
+    /// the original pattern of a verbose regex, when it's stripped
+    /// of its whitespace and comments at runtime
+    pub verbose_pattern: Option<String>,
+    pub flags: RegexFlags,
+    /// whether leftmost-longest semantics were required with the `L` flag
+    pub longest: bool,
+    /// whether the regex must match the whole haystack (`F` flag)
+    pub full: bool,
+    /// whether the group count is part of the type (`typed` option)
+    pub typed: bool,
+    /// whether the regex is a `LiteRegex`, made by a `lite_` macro
+    pub lite: bool,
+    /// whether each thread uses its own clone of the regex (`T` flag)
+    pub local: bool,
+    pub options: RegexOptions,
+    /// the files included in the pattern with `include_str!`
+    pub included_files: Vec<String>,
+}
+
+/// The standard regex flags, given as suffix of the literal
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct RegexFlags {
+    pub case_insensitive: bool,
+    pub multi_line: bool,
+    pub dot_matches_new_line: bool,
+    pub ignore_whitespace: bool,
+    pub swap_greed: bool,
+}
+
+impl RegexFlags {
+    /// Return the letters of the set flags, eg `"ix"`
+    pub fn letters(&self) -> String {
+        [
+            (self.case_insensitive, 'i'),
+            (self.multi_line, 'm'),
+            (self.dot_matches_new_line, 's'),
+            (self.ignore_whitespace, 'x'),
+            (self.swap_greed, 'U'),
+        ]
+        .into_iter()
+        .filter(|(set, _)| *set)
+        .map(|(_, letter)| letter)
+        .collect()
+    }
+    /// Return the expression of the bitset taken by `lazy_regex::build_regex`,
+    /// combining the constants of `lazy_regex::flag`
+    pub fn bits(&self) -> TokenStream2 {
+        let bits: Vec<TokenStream2> = [
+            (self.case_insensitive, quote!(CASE_INSENSITIVE)),
+            (self.multi_line, quote!(MULTI_LINE)),
+            (self.dot_matches_new_line, quote!(DOT_MATCHES_NEW_LINE)),
+            (self.ignore_whitespace, quote!(IGNORE_WHITESPACE)),
+            (self.swap_greed, quote!(SWAP_GREED)),
+        ]
+        .into_iter()
+        .filter(|(set, _)| *set)
+        .map(|(_, name)| quote!(lazy_regex::flag::#name))
+        .collect();
+        if bits.is_empty() {
+            quote!(0)
+        } else {
+            quote!(#(#bits)|*)
+        }
+    }
 }
 
 pub(crate) enum RegexInstance {
@@ -19,77 +112,575 @@ pub(crate) enum RegexInstance {
 
 impl RegexCode {
     pub fn from_token_stream(token_stream: TokenStream, is_bytes: bool) -> Result<Self, syn::Error> {
-        Self::from_lit_str(syn::parse::<syn::LitStr>(token_stream)?, is_bytes)
-    }
-    pub fn from_lit_str(lit_str: LitStr, mut is_bytes: bool) -> Result<Self, syn::Error> {
-        let pattern = lit_str.value();
-        let mut case_insensitive = false;
-        let mut multi_line = false;
-        let mut dot_matches_new_line = false;
-        let mut ignore_whitespace = false;
-        let mut swap_greed = false;
-        for (i, ch) in lit_str.suffix().chars().enumerate() {
+        let RegexArgs { regex_str, options } = syn::parse::<RegexArgs>(token_stream)?;
+        Self::new(regex_str, is_bytes, false, None, &options)
+    }
+    pub fn from_pattern(source: PatternSource, is_bytes: bool) -> Result<Self, syn::Error> {
+        Self::new(source, is_bytes, false, None, &[])
+    }
+    /// Build the code of a `LiteRegex`, for the `lite_` macros
+    pub fn from_token_stream_lite(token_stream: TokenStream) -> Result<Self, syn::Error> {
+        let RegexArgs { regex_str, options } = syn::parse::<RegexArgs>(token_stream)?;
+        Self::new(regex_str, false, true, None, &options)
+    }
+    /// Build the code of a `LiteRegex`, for the `lite_` macros
+    pub fn from_pattern_lite(source: PatternSource) -> Result<Self, syn::Error> {
+        Self::new(source, false, true, None, &[])
+    }
+    /// Build the regex code from the pattern and, optionally, flags
+    /// applying to several regexes (eg all the arms of a switch)
+    pub fn from_pattern_with_flags(
+        source: PatternSource,
+        is_bytes: bool,
+        shared_flags: Option<&Ident>,
+    ) -> Result<Self, syn::Error> {
+        Self::new(source, is_bytes, false, shared_flags, &[])
+    }
+    fn new(
+        source: PatternSource,
+        mut is_bytes: bool,
+        lite: bool,
+        shared_flags: Option<&Ident>,
+        options: &[RegexOption],
+    ) -> Result<Self, syn::Error> {
+        let lit_token = source.lit_token();
+        let PatternSource {
+            value: mut pattern,
+            lit,
+            tokens,
+            included_files,
+        } = source;
+        if matches!(lit, Some(Lit::ByteStr(_))) {
+            is_bytes = true; // a byte string can only be matched as bytes
+        }
+        let mut flags = RegexFlags::default();
+        let mut longest = false;
+        let mut full = false;
+        let mut local = false;
+        let mut apply_flag = |ch| {
             match ch {
-                'i' => case_insensitive = true,
-                'm' => multi_line = true,
-                's' => dot_matches_new_line = true,
-                'x' => ignore_whitespace = true,
-                'U' => swap_greed = true,
+                'i' => flags.case_insensitive = true,
+                'm' => flags.multi_line = true,
+                's' => flags.dot_matches_new_line = true,
+                'x' => flags.ignore_whitespace = true,
+                'U' => flags.swap_greed = true,
                 'B' => is_bytes = true, // non-standard!
-                _ => {
-                    let lit = lit_str.token();
-                    let pos = lit.to_string().len() - i;
+                'L' => longest = true, // non-standard!
+                'F' => full = true, // non-standard!
+                'T' => local = true, // non-standard!
+                _ => return false,
+            }
+            true
+        };
+        if let Some(shared_flags) = shared_flags {
+            for ch in shared_flags.to_string().chars() {
+                if !apply_flag(ch) {
+                    return Err(unrecognized_flag(shared_flags.span(), ch));
+                }
+            }
+        }
+        if let (Some(lit_str), Some(lit)) = (&lit, &lit_token) {
+            let suffix = lit_str.suffix();
+            for (i, ch) in suffix.chars().enumerate() {
+                if !apply_flag(ch) {
+                    let pos = lit.to_string().len() - suffix.len() + i;
                     // subspan only works on nighlty
+                    return Err(unrecognized_flag(
+                        lit.subspan(pos..pos + 1).unwrap_or_else(|| lit.span()),
+                        ch,
+                    ));
+                }
+            }
+        }
+        // flags may also be given as an option, eg when the
+        // pattern is built with concat!
+        for option in options.iter().filter(|o| o.name == "flags") {
+            let letters = match &option.value {
+                Some(Expr::Lit(ExprLit { lit: Lit::Str(letters), .. })) => letters,
+                _ => {
                     return Err(syn::Error::new(
-                        lit.subspan(pos - 1..pos).unwrap_or_else(|| lit.span()),
-                        format!("unrecognized regex flag {ch:?}"),
+                        option.name.span(),
+                        "the flags must be given as a string, eg `flags = \"i\"`",
                     ));
                 }
+            };
+            for ch in letters.value().chars() {
+                if !apply_flag(ch) {
+                    return Err(unrecognized_flag(letters.span(), ch));
+                }
             }
         }
+        // the regex is built at runtime by regex-lite either when it's
+        // the engine of the crate or when a lite macro is used
+        let lite_engine = lite || cfg!(feature = "lite");
+        let options = RegexOptions::new(options, &mut flags, lite_engine)?;
+
+        // the syntax is checked first, for an error pointing at the
+        // offending part of the pattern
+        if let Err(e) = check_syntax(&pattern, is_bytes, &flags, &options) {
+            return Err(syntax_error(lit_token.as_ref(), &tokens, e));
+        }
 
+        // regex-lite must then be the one accepting the regex
+        if lite_engine {
+            let engine = if lite {
+                "the lite macros"
+            } else {
+                "the `lite` feature of lazy-regex"
+            };
+            if is_bytes {
+                return Err(syn::Error::new_spanned(
+                    &tokens,
+                    format!("bytes regexes aren't available with {engine}"),
+                ));
+            }
+            if longest || full {
+                return Err(syn::Error::new_spanned(
+                    &tokens,
+                    format!("the `L` and `F` flags aren't available with {engine}"),
+                ));
+            }
+            if lite && options.typed {
+                return Err(syn::Error::new_spanned(
+                    &tokens,
+                    "the `typed` option isn't available with the lite macros",
+                ));
+            }
+            check_lite(&pattern, &flags, &options)
+                .map_err(|e| syn::Error::new_spanned(&tokens, e))?;
+        }
+
+        // the regex is checked with the configuration it will have at runtime
+        // (with regex-lite, it's only used to know the groups)
+        macro_rules! check {
+            ($builder:ty, $instance:ident) => {{
+                let mut builder = <$builder>::new(&pattern);
+                builder
+                    .case_insensitive(flags.case_insensitive)
+                    .multi_line(flags.multi_line)
+                    .dot_matches_new_line(flags.dot_matches_new_line)
+                    .ignore_whitespace(flags.ignore_whitespace)
+                    .swap_greed(flags.swap_greed);
+                if let Some(yes) = options.crlf {
+                    builder.crlf(yes);
+                }
+                if let Some(byte) = options.line_terminator {
+                    builder.line_terminator(byte);
+                }
+                if let Some(yes) = options.unicode {
+                    builder.unicode(yes);
+                }
+                if let Some(yes) = options.octal {
+                    builder.octal(yes);
+                }
+                if let Some(limit) = options.nest_limit {
+                    builder.nest_limit(limit);
+                }
+                if let Some(bytes) = options.size_limit {
+                    builder.size_limit(bytes);
+                }
+                if let Some(bytes) = options.dfa_size_limit {
+                    builder.dfa_size_limit(bytes);
+                }
+                builder.build().map(RegexInstance::$instance)
+            }};
+        }
         let regex = if is_bytes {
-            regex::bytes::Regex::new(&pattern).map(RegexInstance::Bytes)
+            check!(regex::bytes::RegexBuilder, Bytes)
         } else {
-            regex::Regex::new(&pattern).map(RegexInstance::Regex)
+            check!(regex::RegexBuilder, Regex)
         };
-        let regex = regex.map_err(|e| syn::Error::new(lit_str.span(), e.to_string()))?;
+        let regex = regex.map_err(|e| syn::Error::new_spanned(&tokens, e.to_string()))?;
 
-        let builder_token = if is_bytes {
-            quote!(BytesRegexBuilder)
+        let is_automata = longest || full;
+        let typed = options.typed;
+        if typed && is_automata {
+            return Err(syn::Error::new_spanned(
+                &tokens,
+                "the `typed` option can't be combined with the L or F flags",
+            ));
+        }
+        if local && is_automata {
+            return Err(syn::Error::new_spanned(
+                &tokens,
+                "the T flag can't be combined with the L or F flags",
+            ));
+        }
+        // the pattern of a verbose regex is stripped of its whitespace
+        // and comments, so that it's smaller and faster to parse
+        let mut verbose_pattern = None;
+        let original_flags = flags;
+        if !lite_engine {
+            if let Some((minimal, x)) = minimal_pattern(&pattern, is_bytes, &flags, &options) {
+                verbose_pattern = Some(std::mem::replace(&mut pattern, minimal));
+                flags.ignore_whitespace = x;
+            }
+        }
+        let option_calls = &options.calls;
+        let typing = if typed {
+            let typed_type = typed_type(&regex);
+            quote! {
+                let regex = <lazy_regex:: #typed_type as ::core::convert::TryFrom<_>>::try_from(regex)
+                    .unwrap();
+            }
         } else {
-            quote!(RegexBuilder)
+            quote!()
         };
-        let build = quote! {
-            lazy_regex::Lazy::new(|| {
-                //println!("compiling regex {:?}", #pattern);
-                lazy_regex:: #builder_token ::new(#pattern)
+        // the code building the regex, as an `AutomataRegex` when `automata` is set
+        let build_code = |pattern: &str, flags: &RegexFlags, automata: bool| {
+            if option_calls.is_empty() && !automata && !lite {
+                // the call of a shared function is smaller than the builder calls
+                let bits = flags.bits();
+                let build_fn = if is_bytes {
+                    quote!(build_bytes_regex)
+                } else {
+                    quote!(build_regex)
+                };
+                return quote! {
+                    let regex = lazy_regex:: #build_fn (#pattern, #bits);
+                    #typing
+                    regex
+                };
+            }
+            // the builders of the regex crate are made by a function, as
+            // clippy would check a literal given to their `new` without
+            // knowing the options the pattern was checked with
+            let builder = if lite {
+                quote!(lazy_regex::LiteRegexBuilder::new(#pattern))
+            } else if automata {
+                quote!(lazy_regex::AutomataRegexBuilder::new(#pattern))
+            } else if is_bytes {
+                quote!(lazy_regex::bytes_regex_builder(#pattern))
+            } else {
+                quote!(lazy_regex::regex_builder(#pattern))
+            };
+            let semantics = if automata {
+                quote!(.longest(#longest).full(#full))
+            } else {
+                quote!()
+            };
+            // an `AutomataRegex` is never typed
+            let typing = if automata { quote!() } else { typing.clone() };
+            let RegexFlags {
+                case_insensitive,
+                multi_line,
+                dot_matches_new_line,
+                ignore_whitespace,
+                swap_greed,
+            } = flags;
+            quote! {
+                let regex = #builder
                     .case_insensitive(#case_insensitive)
                     .multi_line(#multi_line)
                     .dot_matches_new_line(#dot_matches_new_line)
                     .ignore_whitespace(#ignore_whitespace)
                     .swap_greed(#swap_greed)
+                    #option_calls
+                    #semantics
                     .build()
-                    .unwrap()
-            })
+                    .unwrap();
+                #typing
+                regex
+            }
+        };
+        // the call sites building the same regex share it, under a key made
+        // of what the build depends on besides the type of the regex
+        let building = |pattern: &str, flags: &RegexFlags, automata: bool| {
+            let semantics = match (automata, longest, full) {
+                (false, _, _) => "",
+                (true, false, false) => "first",
+                (true, true, false) => "longest",
+                (true, false, true) => "full",
+                (true, true, true) => "longest,full",
+            };
+            let key = format!(
+                "{}/{}/{}/{}",
+                flags.letters(),
+                semantics,
+                options.key(),
+                pattern,
+            );
+            let hash = shared_hash(&key);
+            let build = build_code(pattern, flags, automata);
+            quote! {
+                lazy_regex::shared(#hash, #key, || { #build })
+            }
+        };
+        let building = |automata: bool| match &verbose_pattern {
+            // in debug builds, the regex may keep its original pattern, for `as_str()`
+            Some(original) if cfg!(feature = "keep-verbose-patterns") => {
+                let debug = building(original, &original_flags, automata);
+                let release = building(&pattern, &flags, automata);
+                quote! {
+                    #[cfg(debug_assertions)]
+                    let regex = { #debug };
+                    #[cfg(not(debug_assertions))]
+                    let regex = { #release };
+                    regex
+                }
+            }
+            _ => building(&pattern, &flags, automata),
+        };
+        let tracking = track_included_files(&included_files);
+        let lazy_build = |building: TokenStream2| {
+            quote! {
+                lazy_regex::Lazy::new(|| {
+                    //println!("compiling regex {:?}", #pattern);
+                    #tracking
+                    #building
+                })
+            }
         };
-        Ok(Self { build, regex })
+        let build = lazy_build(building(is_automata));
+        // the macros needing the regex-automata engine, like
+        // `regex_find_overlapping!`, build an `AutomataRegex` anyway
+        let automata_build = if is_automata || lite_engine {
+            build.clone()
+        } else {
+            lazy_build(building(true))
+        };
+        Ok(Self {
+            build,
+            automata_build,
+            regex,
+            pattern,
+            verbose_pattern,
+            flags,
+            longest,
+            full,
+            typed,
+            lite,
+            local,
+            options,
+            included_files,
+        })
     }
 }
 
+/// Return the items making the crate rebuilt when a file included in
+/// the pattern changes, to put in all the code embedding the pattern
+/// or values computed from it
+fn track_included_files(included_files: &[String]) -> TokenStream2 {
+    quote! {
+        #(const _: &str = include_str!(#included_files);)*
+    }
+}
+
+/// Return the hash of the key under which the regex is shared between
+/// call sites, the key holding the flags, the options, and the pattern
+fn shared_hash(key: &str) -> u64 {
+    // not randomized, so that the hash is the same in all crates
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Check that regex-lite accepts the regex
+#[cfg(feature = "regex-lite")]
+fn check_lite(pattern: &str, flags: &RegexFlags, options: &RegexOptions) -> Result<(), String> {
+    let mut builder = regex_lite::RegexBuilder::new(pattern);
+    builder
+        .case_insensitive(flags.case_insensitive)
+        .multi_line(flags.multi_line)
+        .dot_matches_new_line(flags.dot_matches_new_line)
+        .ignore_whitespace(flags.ignore_whitespace)
+        .swap_greed(flags.swap_greed);
+    if let Some(yes) = options.crlf {
+        builder.crlf(yes);
+    }
+    if let Some(limit) = options.nest_limit {
+        builder.nest_limit(limit);
+    }
+    if let Some(bytes) = options.size_limit {
+        builder.size_limit(bytes);
+    }
+    builder.build().map(|_| ()).map_err(|e| e.to_string())
+}
+
+/// Without regex-lite, the lite macros can't be used
+#[cfg(not(feature = "regex-lite"))]
+fn check_lite(_: &str, _: &RegexFlags, _: &RegexOptions) -> Result<(), String> {
+    Err("the lite macros need the `lite-macros` feature of lazy-regex".to_string())
+}
+
+/// Return the `TypedRegex` or `TypedBytesRegex` type matching
+/// the groups of the regex
+fn typed_type(regex: &RegexInstance) -> TokenStream2 {
+    match regex {
+        RegexInstance::Regex(regex) => {
+            let n = regex.captures_len() - 1;
+            quote!(TypedRegex<#n>)
+        }
+        RegexInstance::Bytes(regex) => {
+            let n = regex.captures_len() - 1;
+            quote!(TypedBytesRegex<#n>)
+        }
+    }
+}
+
+/// Build the error for a flag which isn't recognized, with a
+/// suggestion when the flag exists as an inline one
+fn unrecognized_flag(span: Span, ch: char) -> syn::Error {
+    let mut message = format!("unrecognized regex flag {ch:?}");
+    if let Some(help) = suffix_flag_help(ch) {
+        message.push_str("\n\nhelp: ");
+        message.push_str(&help);
+    }
+    syn::Error::new(span, message)
+}
+
 impl RegexCode {
+    /// Return the type of the built regex, relative to the lazy_regex crate
+    pub fn regex_type(&self) -> TokenStream2 {
+        if self.lite {
+            return quote!(LiteRegex);
+        }
+        if self.typed {
+            return typed_type(&self.regex);
+        }
+        match (&self.regex, self.is_automata()) {
+            (RegexInstance::Regex(..), false) => quote!(Regex),
+            (RegexInstance::Bytes(..), false) => quote!(BytesRegex),
+            (RegexInstance::Regex(..), true) => quote!(AutomataRegex),
+            (RegexInstance::Bytes(..), true) => quote!(BytesAutomataRegex),
+        }
+    }
+
+    /// Tell whether the regex is an `AutomataRegex` rather than a
+    /// standard `regex::Regex` or `regex::bytes::Regex`, because a
+    /// non-standard semantics was required with the `L` or `F` flag
+    pub fn is_automata(&self) -> bool {
+        self.longest || self.full
+    }
+
+    /// Return the type of the captures given to a replacer closure
+    pub fn captures_type(&self) -> TokenStream2 {
+        if self.lite {
+            return quote!(lazy_regex::LiteCaptures<'_>);
+        }
+        match (&self.regex, self.is_automata()) {
+            (RegexInstance::Regex(..), false) => quote!(lazy_regex::Captures<'_>),
+            (RegexInstance::Bytes(..), false) => quote!(lazy_regex::regex::bytes::Captures<'_>),
+            (RegexInstance::Regex(..), true) => quote!(lazy_regex::AutomataCaptures<'_, str>),
+            (RegexInstance::Bytes(..), true) => quote!(lazy_regex::AutomataCaptures<'_, [u8]>),
+        }
+    }
+
     pub fn statick(&self) -> TokenStream2 {
         let build = &self.build;
+        let regex_token = self.regex_type();
+        let error = self.local_unsupported();
+        quote! {
+            #error
+            static RE: lazy_regex::Lazy<lazy_regex:: #regex_token > = #build;
+        }
+    }
+
+    /// Declare the `RE` static of the regex built as an `AutomataRegex`,
+    /// for the macros needing the regex-automata engine
+    pub fn automata_statick(&self) -> TokenStream2 {
+        if self.lite || cfg!(feature = "lite") {
+            return syn::Error::new(
+                Span::call_site(),
+                "this macro isn't available with the `lite` feature",
+            ).to_compile_error();
+        }
+        let build = &self.automata_build;
         let regex_token = match self.regex {
-            RegexInstance::Regex(..) => quote!(Regex),
-            RegexInstance::Bytes(..) => quote!(BytesRegex),
+            RegexInstance::Regex(..) => quote!(AutomataRegex),
+            RegexInstance::Bytes(..) => quote!(BytesAutomataRegex),
         };
+        let error = self.local_unsupported();
         quote! {
+            #error
             static RE: lazy_regex::Lazy<lazy_regex:: #regex_token > = #build;
         }
     }
 
+    /// Return the items making the crate rebuilt when an included file
+    /// changes, for the code embedding the pattern without `build`
+    pub fn tracking(&self) -> TokenStream2 {
+        track_included_files(&self.included_files)
+    }
+
+    /// Wrap a value computed from the pattern at compile time in a
+    /// block with the tracking of the included files, if any
+    pub fn tracked(&self, value: TokenStream2) -> TokenStream2 {
+        if self.included_files.is_empty() {
+            return value;
+        }
+        let tracking = self.tracking();
+        quote!({
+            #tracking
+            #value
+        })
+    }
+
+    /// Return the lazy static initializer, for the macros declaring
+    /// their own static
+    pub fn lazy(&self) -> TokenStream2 {
+        if self.local {
+            return self.local_unsupported();
+        }
+        self.build.clone()
+    }
+
+    /// With the `T` flag, return the error of a macro which can't give
+    /// the regex of the current thread, because it returns a value
+    /// borrowing it or declares a static
+    fn local_unsupported(&self) -> TokenStream2 {
+        if !self.local {
+            return quote!();
+        }
+        syn::Error::new(
+            Span::call_site(),
+            "the T flag isn't supported by this macro",
+        ).to_compile_error()
+    }
+
+    /// Declare the `LOCAL` thread local holding, for each thread, a
+    /// clone of the shared lazy static regex (`T` flag)
+    fn local_statics(&self) -> TokenStream2 {
+        let build = &self.build;
+        let regex_token = self.regex_type();
+        quote! {
+            static SHARED: lazy_regex::Lazy<lazy_regex:: #regex_token > = #build;
+            ::std::thread_local! {
+                static LOCAL: lazy_regex:: #regex_token = ::core::clone::Clone::clone(&*SHARED);
+            }
+        }
+    }
+
+    /// Wrap an expression using the regex as `RE`: after the static
+    /// declaration or, with the `T` flag, in a closure given the
+    /// regex of the current thread
+    pub fn scoped(&self, body: TokenStream2) -> TokenStream2 {
+        if !self.local {
+            let statick = self.statick();
+            return quote! {{
+                #statick;
+                #body
+            }};
+        }
+        let local_statics = self.local_statics();
+        quote! {{
+            #local_statics
+            LOCAL.with(|regex| {
+                #[allow(non_snake_case)]
+                let RE = regex;
+                #body
+            })
+        }}
+    }
+
     pub fn lazy_static(&self) -> TokenStream2 {
+        if self.local {
+            let local_statics = self.local_statics();
+            return quote! {{
+                #local_statics
+                &LOCAL
+            }};
+        }
         let statick = self.statick();
         quote! {{
             #statick;
@@ -103,6 +694,46 @@ impl RegexCode {
             RegexInstance::Bytes(regex) => regex.captures_len(),
         }
     }
+    /// Return an error telling that the number of groups doesn't
+    /// match the number of bindings or arguments given by the user,
+    /// or `None` when it matches
+    pub fn check_arity(
+        &self,
+        given: usize,
+        what: &str,
+        tokens: impl quote::ToTokens,
+    ) -> Option<syn::Error> {
+        let n = self.captures_len();
+        if given == n {
+            return None;
+        }
+        let groups: Vec<String> = self
+            .group_names()
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| match (i, name) {
+                (0, _) => "0: whole".to_string(),
+                (i, Some(name)) => format!("{i}: {name}"),
+                (i, None) => format!("{i}: -"),
+            })
+            .collect();
+        Some(syn::Error::new_spanned(
+            tokens,
+            format!(
+                "pattern has {n} groups ({}) but {given} {what}{} given",
+                groups.join(", "),
+                if given == 1 { " was" } else { "s were" },
+            ),
+        ))
+    }
+    /// Return the names of all groups, `None` for the unnamed ones
+    /// (including the implicit group of the whole match)
+    pub fn group_names(&self) -> Vec<Option<&str>> {
+        match &self.regex {
+            RegexInstance::Regex(regex) => regex.capture_names().collect(),
+            RegexInstance::Bytes(regex) => regex.capture_names().collect(),
+        }
+    }
     pub fn named_groups(&self) -> Vec<(usize, &str)> {
         match &self.regex {
             RegexInstance::Regex(regex) => regex
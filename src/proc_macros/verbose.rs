@@ -0,0 +1,62 @@
+use {
+    crate::{
+        diagnostic::hir_parser,
+        options::RegexOptions,
+        regex_code::RegexFlags,
+    },
+    regex_syntax::ast::{
+        parse::ParserBuilder,
+        print::Printer,
+    },
+};
+
+/// Return the pattern without the whitespace and comments of the
+/// verbose mode (`x` flag), and whether it still needs the `x` flag,
+/// when it's shorter and it's the same regex.
+///
+/// The pattern is printed from its AST, which has no comment, and
+/// both patterns are checked to give the same HIR.
+pub(crate) fn minimal_pattern(
+    pattern: &str,
+    is_bytes: bool,
+    flags: &RegexFlags,
+    options: &RegexOptions,
+) -> Option<(String, bool)> {
+    // without the flag, the verbose mode can only be set inline
+    if !flags.ignore_whitespace && !pattern.contains("(?") {
+        return None;
+    }
+    let mut parser = ParserBuilder::new();
+    parser.ignore_whitespace(flags.ignore_whitespace);
+    if let Some(yes) = options.octal {
+        parser.octal(yes);
+    }
+    if let Some(limit) = options.nest_limit {
+        parser.nest_limit(limit);
+    }
+    let ast = parser.build().parse(pattern).ok()?;
+    let mut minimal = String::new();
+    Printer::new().print(&ast, &mut minimal).ok()?;
+    if minimal.len() >= pattern.len() {
+        return None;
+    }
+    let hir = hir_parser(is_bytes, flags, options).parse(pattern).ok()?;
+    let same_hir = |flags: &RegexFlags| {
+        hir_parser(is_bytes, flags, options)
+            .parse(&minimal)
+            .is_ok_and(|h| h == hir)
+    };
+    // the `x` flag is kept only when the minimal pattern needs it,
+    // eg for an escaped space
+    let without_x = RegexFlags {
+        ignore_whitespace: false,
+        ..*flags
+    };
+    if same_hir(&without_x) {
+        Some((minimal, false))
+    } else if flags.ignore_whitespace && same_hir(flags) {
+        Some((minimal, true))
+    } else {
+        None
+    }
+}
@@ -0,0 +1,49 @@
+use {
+    alloc::boxed::Box,
+    core::{
+        fmt,
+        ops::Deref,
+    },
+    once_cell::race::OnceBox,
+};
+
+/// A value initialized on first access, used for the lazy statics
+/// when neither the `std` nor the `lazy-lock` feature is enabled, eg in `no_std`.
+///
+/// It's backed by [`OnceBox`], which doesn't need `std` nor a lock:
+/// several threads racing on the first access may each compute the
+/// value, but only one of them is kept. That's why the initializer is
+/// a `Fn` rather than a `FnOnce`.
+pub struct Lazy<T, F = fn() -> T> {
+    cell: OnceBox<T>,
+    init: F,
+}
+
+impl<T, F> Lazy<T, F> {
+    pub const fn new(init: F) -> Self {
+        Self {
+            cell: OnceBox::new(),
+            init,
+        }
+    }
+}
+
+impl<T, F: Fn() -> T> Lazy<T, F> {
+    /// Initialize the value if it's not yet done, and return it
+    pub fn force(this: &Self) -> &T {
+        this.cell.get_or_init(|| Box::new((this.init)()))
+    }
+}
+
+impl<T, F: Fn() -> T> Deref for Lazy<T, F> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        Self::force(self)
+    }
+}
+
+impl<T: fmt::Debug, F> fmt::Debug for Lazy<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Lazy").field(&self.cell.get()).finish()
+    }
+}
@@ -0,0 +1,67 @@
+#[cfg(any(feature = "std", feature = "lazy-lock", feature = "lite"))]
+use std::{
+    any::{
+        Any,
+        TypeId,
+    },
+    boxed::Box,
+    collections::BTreeMap,
+    sync::{
+        Mutex,
+        PoisonError,
+    },
+    vec::Vec,
+};
+
+/// The regexes built by the macros, by hash of their key and by type,
+/// with their key, the code building them, to rule out collisions
+#[cfg(any(feature = "std", feature = "lazy-lock", feature = "lite"))]
+#[allow(clippy::type_complexity)]
+static REGISTRY: Mutex<BTreeMap<(u64, TypeId), Vec<(&'static str, Box<dyn Any + Send + Sync>)>>> =
+    Mutex::new(BTreeMap::new());
+
+/// Return a clone of the regex already built by another call site
+/// with the same key, which is the code building the regex, so holds
+/// its pattern and options, or build and register it.
+///
+/// This is called once per call site, by the initializer of its lazy
+/// static, so that identical regexes share their compiled program
+/// while each use still only costs the deref of a static.
+#[doc(hidden)]
+#[cfg(any(feature = "std", feature = "lazy-lock", feature = "lite"))]
+pub fn shared<R>(hash: u64, key: &'static str, build: fn() -> R) -> R
+where
+    R: Clone + Send + Sync + 'static,
+{
+    let id = (hash, TypeId::of::<R>());
+    let find = |registry: &BTreeMap<_, Vec<(&'static str, Box<dyn Any + Send + Sync>)>>| {
+        registry
+            .get(&id)?
+            .iter()
+            .find(|(k, _)| *k == key)
+            .and_then(|(_, regex)| regex.downcast_ref::<R>())
+            .cloned()
+    };
+    if let Some(regex) = find(&REGISTRY.lock().unwrap_or_else(PoisonError::into_inner)) {
+        return regex;
+    }
+    // the lock isn't held while building, which may be long, or panic
+    let regex = build();
+    let mut registry = REGISTRY.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(regex) = find(&registry) {
+        // another thread registered it in the meantime
+        return regex;
+    }
+    registry
+        .entry(id)
+        .or_default()
+        .push((key, Box::new(regex.clone())));
+    regex
+}
+
+/// Build the regex, without sharing it as there's no lock in `no_std`
+#[doc(hidden)]
+#[cfg(not(any(feature = "std", feature = "lazy-lock", feature = "lite")))]
+pub fn shared<R>(_hash: u64, _key: &'static str, build: fn() -> R) -> R {
+    build()
+}
@@ -0,0 +1,187 @@
+use {
+    super::{
+        regex,
+        Lazy,
+    },
+    core::{
+        fmt,
+        ops::Deref,
+    },
+};
+
+/// A regex whose number of capture groups, not counting the implicit
+/// group of the whole match, is part of its type.
+///
+/// This is what `regex!` returns with the `typed` option:
+///
+/// ```
+/// # use lazy_regex::*;
+/// fn key_value<'h>(r: &TypedRegex<2>, text: &'h str) -> Option<(&'h str, &'h str)> {
+///     let [key, value] = r.captures(text)?;
+///     Some((key, value))
+/// }
+/// let r: &TypedRegex<2> = regex!(r"(\w+)=(\w*)", typed);
+/// assert_eq!(key_value(r, "a=b"), Some(("a", "b")));
+/// ```
+///
+/// It derefs to the underlying [`Regex`](regex::Regex).
+#[derive(Clone)]
+pub struct TypedRegex<const N: usize> {
+    regex: regex::Regex,
+}
+
+impl<const N: usize> TypedRegex<N> {
+    /// Return the wrapped regex
+    pub fn into_inner(self) -> regex::Regex {
+        self.regex
+    }
+    /// Return the captured groups of the leftmost match, if any.
+    ///
+    /// An optional group with no value is given as `""`.
+    pub fn captures<'h>(&self, haystack: &'h str) -> Option<[&'h str; N]> {
+        let caps = self.regex.captures(haystack)?;
+        Some(core::array::from_fn(|i| {
+            caps.get(i + 1).map_or("", |m| m.as_str())
+        }))
+    }
+    /// Return an iterator over the captured groups of the
+    /// successive non-overlapping matches
+    pub fn captures_iter<'r, 'h>(
+        &'r self,
+        haystack: &'h str,
+    ) -> impl Iterator<Item = [&'h str; N]> + 'r
+    where
+        'h: 'r,
+    {
+        self.regex.captures_iter(haystack).map(|caps| {
+            core::array::from_fn(|i| caps.get(i + 1).map_or("", |m| m.as_str()))
+        })
+    }
+}
+
+/// A regex type whose number of capture groups is known at compile time.
+///
+/// It's implemented by [`TypedRegex`], [`TypedBytesRegex`], and the lazy
+/// statics holding them, so the group layout of a static declared with
+/// `lazy_regex!` and the `typed` option can be checked in a `const` assertion:
+///
+/// ```
+/// # use lazy_regex::*;
+/// pub static KEY_VALUE: Lazy<TypedRegex<2>> = lazy_regex!(r"(\w+)=(\w*)", typed);
+/// const _: () = assert!(<Lazy<TypedRegex<2>>>::CAPTURES_LEN == 3);
+/// fn captures_len<R: CapturesLen>(_: &R) -> usize {
+///     R::CAPTURES_LEN
+/// }
+/// assert_eq!(captures_len(&KEY_VALUE), KEY_VALUE.captures_len());
+/// ```
+pub trait CapturesLen {
+    /// The number of groups, including the implicit group of the
+    /// whole match, as returned by `captures_len()`
+    const CAPTURES_LEN: usize;
+}
+
+impl<const N: usize> CapturesLen for TypedRegex<N> {
+    const CAPTURES_LEN: usize = N + 1;
+}
+
+impl<R: CapturesLen, F> CapturesLen for Lazy<R, F> {
+    const CAPTURES_LEN: usize = R::CAPTURES_LEN;
+}
+
+/// Check the number of groups, giving the regex back if it's not `N`
+impl<const N: usize> TryFrom<regex::Regex> for TypedRegex<N> {
+    type Error = regex::Regex;
+    fn try_from(regex: regex::Regex) -> Result<Self, Self::Error> {
+        if regex.captures_len() == N + 1 {
+            Ok(Self { regex })
+        } else {
+            Err(regex)
+        }
+    }
+}
+
+impl<const N: usize> Deref for TypedRegex<N> {
+    type Target = regex::Regex;
+    fn deref(&self) -> &Self::Target {
+        &self.regex
+    }
+}
+
+impl<const N: usize> fmt::Debug for TypedRegex<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TypedRegex").field(&self.regex.as_str()).finish()
+    }
+}
+
+/// A bytes regex whose number of capture groups, not counting
+/// the implicit group of the whole match, is part of its type.
+///
+/// This is what `bytes_regex!` returns with the `typed` option.
+#[cfg(not(feature = "lite"))]
+#[derive(Clone)]
+pub struct TypedBytesRegex<const N: usize> {
+    regex: regex::bytes::Regex,
+}
+
+#[cfg(not(feature = "lite"))]
+impl<const N: usize> TypedBytesRegex<N> {
+    /// Return the wrapped regex
+    pub fn into_inner(self) -> regex::bytes::Regex {
+        self.regex
+    }
+    /// Return the captured groups of the leftmost match, if any.
+    ///
+    /// An optional group with no value is given as `b""`.
+    pub fn captures<'h>(&self, haystack: &'h [u8]) -> Option<[&'h [u8]; N]> {
+        let caps = self.regex.captures(haystack)?;
+        Some(core::array::from_fn(|i| {
+            caps.get(i + 1).map_or(&b""[..], |m| m.as_bytes())
+        }))
+    }
+    /// Return an iterator over the captured groups of the
+    /// successive non-overlapping matches
+    pub fn captures_iter<'r, 'h>(
+        &'r self,
+        haystack: &'h [u8],
+    ) -> impl Iterator<Item = [&'h [u8]; N]> + 'r
+    where
+        'h: 'r,
+    {
+        self.regex.captures_iter(haystack).map(|caps| {
+            core::array::from_fn(|i| caps.get(i + 1).map_or(&b""[..], |m| m.as_bytes()))
+        })
+    }
+}
+
+#[cfg(not(feature = "lite"))]
+impl<const N: usize> CapturesLen for TypedBytesRegex<N> {
+    const CAPTURES_LEN: usize = N + 1;
+}
+
+/// Check the number of groups, giving the regex back if it's not `N`
+#[cfg(not(feature = "lite"))]
+impl<const N: usize> TryFrom<regex::bytes::Regex> for TypedBytesRegex<N> {
+    type Error = regex::bytes::Regex;
+    fn try_from(regex: regex::bytes::Regex) -> Result<Self, Self::Error> {
+        if regex.captures_len() == N + 1 {
+            Ok(Self { regex })
+        } else {
+            Err(regex)
+        }
+    }
+}
+
+#[cfg(not(feature = "lite"))]
+impl<const N: usize> Deref for TypedBytesRegex<N> {
+    type Target = regex::bytes::Regex;
+    fn deref(&self) -> &Self::Target {
+        &self.regex
+    }
+}
+
+#[cfg(not(feature = "lite"))]
+impl<const N: usize> fmt::Debug for TypedBytesRegex<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TypedBytesRegex").field(&self.regex.as_str()).finish()
+    }
+}
@@ -0,0 +1,41 @@
+use {
+    lazy_regex::*,
+};
+
+// The regexes without builder option are built by `build_regex`,
+// with the bits of their flags
+
+#[test]
+fn test_build_regex_flags() {
+    let r = build_regex("ab", 0);
+    assert!(!r.is_match("AB"));
+    let r = build_regex("ab", flag::CASE_INSENSITIVE);
+    assert!(r.is_match("AB"));
+    let r = build_regex("^b$", flag::MULTI_LINE);
+    assert!(r.is_match("a\nb"));
+    let r = build_regex("a.b", flag::DOT_MATCHES_NEW_LINE);
+    assert!(r.is_match("a\nb"));
+    let r = build_regex("a b # comment", flag::IGNORE_WHITESPACE);
+    assert!(r.is_match("ab"));
+    let r = build_regex("a+", flag::SWAP_GREED);
+    assert_eq!(r.find("aaa").unwrap().as_str(), "a");
+    let r = build_regex("^A.B$", flag::CASE_INSENSITIVE | flag::MULTI_LINE | flag::DOT_MATCHES_NEW_LINE);
+    assert!(r.is_match("x\na\nb"));
+}
+
+#[test]
+fn test_build_regex_as_macros() {
+    // the macros give the same flags to build_regex
+    assert_eq!(regex!("^a.b$"ims).as_str(), build_regex("^a.b$", 0).as_str());
+    assert!(regex!("^a.b$"ims).is_match("x\nA\nB"));
+    assert_eq!(regex!("a+"U).find("aaa").unwrap().as_str(), "a");
+}
+
+#[cfg(not(feature = "lite"))]
+#[test]
+fn test_build_bytes_regex_flags() {
+    let r = build_bytes_regex("ab", flag::CASE_INSENSITIVE);
+    assert!(r.is_match(b"AB"));
+    let r = build_bytes_regex("a b", flag::IGNORE_WHITESPACE | flag::SWAP_GREED);
+    assert!(r.is_match(b"ab"));
+}
@@ -0,0 +1,53 @@
+#![cfg(not(feature = "lite"))]
+
+use {
+    lazy_regex::*,
+};
+
+#[test]
+fn test_byte_string_patterns() {
+    let r = bytes_regex!(b"\xFF\xFE(?-u:.)+");
+    assert!(r.is_match(b"\xFF\xFEa\x00\x80"));
+    assert!(!r.is_match("\u{FF}\u{FE}a".as_bytes()));
+    // a byte string implies a bytes regex
+    let r: &BytesRegex = regex!(b"^\x89PNG\r\n\x1A\n");
+    assert!(r.is_match(b"\x89PNG\r\n\x1A\n...."));
+    // flags may be given as suffix
+    let r = bytes_regex!(b"^gif8[79]A\xC3"i);
+    assert!(r.is_match(b"GIF89a\xC3"));
+    // escaped raw bytes still match themselves
+    assert!(bytes_regex!(b"a\\\xE9").is_match(b"a\xE9"));
+}
+
+#[test]
+fn test_byte_string_classes() {
+    let r = bytes_regex!(b"^(?-u:[\x80-\xFF]+)$");
+    assert!(r.is_match(b"\x80\xC3\xFF"));
+    assert!(!r.is_match(b"\x80a"));
+    let r = bytes_regex!(b"[]a]\xFF");
+    assert!(r.is_match(b"]\xFF"));
+    // a class holding raw bytes matches them, not the chars of the same codes
+    let r = bytes_regex!(b"^[\xFE\xFF]$");
+    assert!(r.is_match(b"\xFF"));
+    assert!(!r.is_match("\u{FF}".as_bytes()));
+    let r = bytes_regex!(b"^[^a\x80-\xFF]+$");
+    assert!(r.is_match(b"bc\x7F"));
+    assert!(!r.is_match(b"b\xC3"));
+    let r = bytes_regex!(b"^[a-c[\xE9]]+\xE9$");
+    assert!(r.is_match(b"ab\xE9\xE9"));
+}
+
+#[test]
+fn test_byte_string_switch() {
+    let kind = |header: &[u8]| {
+        bytes_regex_switch!(header,
+            b"^\x89PNG" => "png",
+            b"^\xFF\xD8\xFF" => "jpeg",
+            "^GIF8[79]a" => "gif",
+        )
+    };
+    assert_eq!(kind(b"\xFF\xD8\xFF\xE0"), Some("jpeg"));
+    assert_eq!(kind(b"\x89PNG"), Some("png"));
+    assert_eq!(kind(b"GIF87a"), Some("gif"));
+    assert_eq!(kind(b"\xFF"), None);
+}
@@ -0,0 +1,50 @@
+use {
+    lazy_regex::*,
+};
+
+#[test]
+fn test_captures_with_bindings() {
+    let version = regex_captures!(
+        r"(?<name>\w+)-(?<version>[0-9.]+)",
+        "This is lazy_regex-2.0!" => (whole, name, version) => (whole.len(), name, version),
+    );
+    assert_eq!(version, Some((14, "lazy_regex", "2.0")));
+    // the bindings may be ignored, or partly given with a rest pattern
+    let caps = regex_captures!(r"(\d)(\d)", "a12" => (_, a, _) => a);
+    assert_eq!(caps, Some("1"));
+    let caps = regex_captures!(r"(\d)(\d)", "a12" => (whole, ..) => whole);
+    assert_eq!(caps, Some("12"));
+    // a pattern without group gives the whole match
+    assert_eq!(regex_captures!(r"\d+", "a12" => number => number.len()), Some(2));
+    // the text may be any expression
+    let text = String::from("b=3");
+    let n = regex_captures!(r"(\w)=(\d)", &text => (_, _, n) => n.parse::<u32>().unwrap());
+    assert_eq!(n, Some(3));
+    assert_eq!(regex_captures!(r"(\w)=(\d)", &text[..1] => (_, k, _) => k), None);
+}
+
+#[test]
+fn test_captures_with_bindings_only() {
+    // without an expression, the value is the tuple of the bound names
+    let version = regex_captures!(
+        r"(?<name>\w+)-(?<version>[0-9.]+)",
+        "This is lazy_regex-2.0!" => (_, name, version)
+    );
+    assert_eq!(version, Some(("lazy_regex", "2.0")));
+    let all = regex_captures!(r"(\d)(\d)", "a12" => (whole, a, b));
+    assert_eq!(all, Some(("12", "1", "2")));
+    // or the name, when there's only one
+    let text = String::from("b=3");
+    let n = regex_captures!(r"(\w)=(\d)", &text => (_, _, n));
+    assert_eq!(n, Some("3"));
+    assert_eq!(regex_captures!(r"(\w)=(\d)", &text[..1] => (_, k, _)), None);
+}
+
+#[cfg(not(feature = "lite"))]
+#[test]
+fn test_bytes_captures_with_bindings() {
+    let major = bytes_regex_captures!(r"v(\d+)", b"v42" => (_, major) => major);
+    assert_eq!(major, Some(&b"42"[..]));
+    let major = bytes_regex_captures!(r"v(\d+)", b"v42" => (_, major));
+    assert_eq!(major, Some(&b"42"[..]));
+}
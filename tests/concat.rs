@@ -0,0 +1,54 @@
+use {
+    lazy_regex::*,
+};
+
+macro_rules! find_number {
+    ($prefix:literal, $text:expr) => {
+        regex_find!(concat!($prefix, r"\d+"), $text)
+    };
+}
+
+#[test]
+fn test_concat() {
+    let r = regex!(concat!(r"^v", r"\d+", r"(\.\d+)*$"));
+    assert!(r.is_match("v1.22.3"));
+    assert!(regex_is_match!(concat!("^a", 'b', 3, "$"), "ab3"));
+    // flags are given with an option
+    let r = regex!(concat!("^a", "b+$"), flags = "i");
+    assert!(r.is_match("ABB"));
+    // nested macros
+    let r = regex!(concat!("^", concat!("x", "y"), "$"));
+    assert!(r.is_match("xy"));
+    // a fragment coming from a macro_rules
+    assert_eq!(find_number!("#", "ab 12 #34"), Some("#34"));
+}
+
+#[test]
+fn test_include_str() {
+    let (_, year, month, day) = regex_captures!(
+        include_str!("tests/patterns/date.re"),
+        "on 2026-10-18",
+    ).unwrap();
+    assert_eq!(year, "2026");
+    assert_eq!(month, "10");
+    assert_eq!(day, "18");
+    let r = regex!(concat!("^", include_str!("tests/patterns/date.re"), "$"));
+    assert!(r.is_match("1970-01-01"));
+    assert!(!r.is_match("1970-01-01 "));
+    // the values computed at compile time also depend on the file
+    const GROUPS: usize = regex_captures_len!(include_str!("tests/patterns/date.re"));
+    assert_eq!(GROUPS, 4);
+    assert!(regex_is_match!(include_str!("tests/patterns/date.re"), "2026-10-18"));
+}
+
+#[test]
+fn test_concat_in_switch() {
+    fn kind(s: &str) -> Option<&'static str> {
+        regex_switch!(s,
+            concat!("^", include_str!("tests/patterns/date.re"), "$") => "date",
+            r"^\d+$" => "number",
+        )
+    }
+    assert_eq!(kind("2000-12-31"), Some("date"));
+    assert_eq!(kind("42"), Some("number"));
+}
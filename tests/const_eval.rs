@@ -0,0 +1,44 @@
+#![cfg(not(feature = "lite"))]
+
+use {
+    lazy_regex::*,
+};
+
+// With literal texts, the results are computed at compile time,
+// so that they can be used in constants
+
+const IS_VERSION: bool = regex_is_match!(r"^v\d+$", "v12");
+const VERSION: Option<(&str, &str)> = regex_captures!(r"^v(\d+)$", "v12");
+const NAME: Option<&str> = regex_find!(r"[a-z]+", "12-lazy-regex");
+
+const _: () = assert!(IS_VERSION);
+const _: () = assert!(!regex_is_match!(r"^v\d+$", "version 12"));
+const _: () = assert!(bytes_regex_is_match!("^GIF8[79]a", b"GIF89a"));
+
+#[test]
+fn test_const_eval() {
+    assert_eq!(VERSION, Some(("v12", "12")));
+    assert_eq!(VERSION.is_some(), IS_VERSION);
+    assert_eq!(NAME, Some("lazy"));
+    // optional groups without value are empty
+    assert_eq!(regex_captures!("(a)|(b)", "b"), Some(("b", "", "b")));
+    // flags are applied
+    assert_eq!(regex_find!("REGEX"i, "lazy-regex"), Some("regex"));
+}
+
+#[test]
+fn test_const_eval_bytes() {
+    const VERSION: Option<(&[u8], &[u8])> = bytes_regex_captures!(r"^GIF(8[79])a", b"GIF87a...");
+    assert_eq!(VERSION, Some((&b"GIF87a"[..], &b"87"[..])));
+    assert_eq!(bytes_regex_find!(b"\xFF+", b"ab\xFF\xFFcd"), Some(&b"\xFF\xFF"[..]));
+}
+
+#[test]
+fn test_never_matches_warning_can_be_silenced() {
+    #[allow(deprecated)]
+    let none = regex_find!(r"^v\d+$", "version 12");
+    assert_eq!(none, None);
+    #[allow(deprecated)]
+    let none = bytes_regex_captures!(r"^v(\d+)$", b"version 12");
+    assert_eq!(none, None);
+}
@@ -0,0 +1,61 @@
+#![cfg(not(feature = "lite"))]
+
+use {
+    lazy_regex::*,
+};
+
+#[test]
+fn test_dfa_regex() {
+    let r: &DfaRegex = dfa_regex!(r"(\d{4})-(\d{2})-(\d{2})");
+    assert!(r.is_precompiled());
+    assert_eq!(r.as_str(), r"(\d{4})-(\d{2})-(\d{2})");
+    assert!(r.is_match("on 2026-10-18"));
+    assert_eq!(r.find("on 2026-10-18.").unwrap().range(), 3..13);
+    assert!(r.find("on 18/10/2026").is_none());
+    // flags and options apply to the precompiled DFAs
+    let r = dfa_regex!("^ab+$"i);
+    assert!(r.is_precompiled());
+    assert!(r.is_match("aBB"));
+    let r = dfa_regex!("^a.b$", dot_matches_new_line);
+    assert!(r.is_match("a\nb"));
+    let r = dfa_regex!(include_str!("tests/patterns/date.re"));
+    assert!(r.is_match("on 2026-10-18"));
+}
+
+#[test]
+fn test_dfa_regex_leftmost_first() {
+    let r = dfa_regex!("sam|samwise");
+    assert_eq!(r.find("samwise").unwrap().as_str(), "sam");
+    // empty matches don't split chars
+    let r = dfa_regex!("");
+    assert_eq!(r.find("é").unwrap().range(), 0..0);
+}
+
+#[test]
+fn test_dfa_regex_bytes() {
+    let r: &BytesDfaRegex = dfa_regex!(r"^GIF8[79]a"B);
+    assert!(r.is_precompiled());
+    assert!(r.is_match(b"GIF89a\xFF"));
+    let r = dfa_regex!(b"\xFF\xD8\xFF");
+    assert_eq!(r.find(b"..\xFF\xD8\xFF").unwrap().as_bytes(), b"\xFF\xD8\xFF");
+}
+
+#[test]
+fn test_dfa_regex_fallback() {
+    // DFAs don't support Unicode word boundaries
+    let r = dfa_regex!(r"\bété\b");
+    assert!(!r.is_precompiled());
+    assert!(r.is_match("un été chaud"));
+    // a DFA too big for the limit
+    let r = dfa_regex!(r"\w{50}", dfa_size_limit = 1000);
+    assert!(!r.is_precompiled());
+    assert!(r.is_match(&"a".repeat(50)));
+    assert!(!r.is_match(&"a".repeat(49)));
+}
+
+#[test]
+fn test_dfa_regex_is_match() {
+    assert!(dfa_regex_is_match!("[ab]+", "car"));
+    assert!(!dfa_regex_is_match!("^[ab]+$", "car"));
+    assert!(dfa_regex_is_match!(r"^\d+$"B, b"123"));
+}
@@ -0,0 +1,86 @@
+#![cfg(not(feature = "lite"))]
+
+use {
+    lazy_regex::*,
+    std::borrow::Cow,
+};
+
+#[test]
+fn test_full_is_match() {
+    assert!(regex_is_match!(r"\d+"F, "1234"));
+    assert!(!regex_is_match!(r"\d+"F, "1234a"));
+    assert!(!regex_is_match!(r"\d+"F, "a1234"));
+    assert!(regex_is_match!("ab+"iF, "aBB"));
+    // a shorter preferred branch doesn't prevent a full match
+    assert!(regex_is_match!("a|ab"F, "ab"));
+    assert!(regex_is_match!(""F, ""));
+    assert!(!regex_is_match!(""F, "a"));
+    assert!(bytes_regex_is_match!(r"\w+"F, b"abc"));
+    assert!(!bytes_regex_is_match!(r"\w+"F, b"abc "));
+    assert!(regex_is_match!(r"\d+"BF, b"42"));
+}
+
+#[test]
+fn test_full_find_and_captures() {
+    assert_eq!(regex_find!(r"\w+"F, "word"), Some("word"));
+    assert_eq!(regex_find!(r"\w+"F, "two words"), None);
+    let (whole, key, value) = regex_captures!(r"(\w+)=(\w*)"F, "a=b").unwrap();
+    assert_eq!(whole, "a=b");
+    assert_eq!(key, "a");
+    assert_eq!(value, "b");
+    assert_eq!(regex_captures!(r"(\w+)=(\w*)"F, "a=b c=d"), None);
+    // the groups are the ones of the preferred branch when it matches everything
+    let (_, first, second) = regex_captures!("(a+)|(a+b)"F, "aab").unwrap();
+    assert_eq!(first, "");
+    assert_eq!(second, "aab");
+    let (_, word) = bytes_regex_captures!(r"<(\w+)>"F, b"<tag>").unwrap();
+    assert_eq!(word, b"tag");
+}
+
+#[test]
+fn test_full_other_macros() {
+    assert_eq!(regex_rfind!(r"\d+"F, "123"), Some("123"));
+    assert_eq!(regex_find_overlapping!("a+"F, "aaa").count(), 1);
+    assert_eq!(regex_captures_iter!(r"(\d)+"F, "123").count(), 1);
+    assert_eq!(regex_replace!(r"(\w+)@(\w+)"F, "me@here", "$2:$1"), "here:me");
+    assert_eq!(
+        regex_replace_all!(r"(\w+)@(\w+)"F, "me@here", |_, a: &str, b| format!("{b}/{a}")),
+        "here/me",
+    );
+    assert_eq!(regex_replace!(r"\w+"F, "no match", "x"), "no match");
+    assert_eq!(bytes_regex_replace!("f(u*)"iF, b"Fuu", b"c$1"), &b"cuu"[..]);
+    assert_eq!(regex_remove!(r"\s*\w+\s*"F, " word "), "");
+    assert!(matches!(regex_remove_all!(r"\w+"F, "two words"), Cow::Borrowed("two words")));
+    assert_eq!(bytes_regex_remove!(r"\d+"F, b"12"), &b""[..]);
+    assert_eq!(regex_if!(r"(?<n>\d+)"F, "12", n.len()), Some(2));
+    assert_eq!(regex_if!(r"(?<n>\d+)"F, "12 ", n.len()), None);
+}
+
+#[test]
+fn test_full_switch() {
+    fn kind(s: &str) -> Option<&str> {
+        regex_switch!(s, F,
+            r"\d+" => "number",
+            r"[a-z]+"i => "word",
+            r"(?<c>.)" => c,
+        )
+    }
+    assert_eq!(kind("123"), Some("number"));
+    assert_eq!(kind("Abc"), Some("word"));
+    assert_eq!(kind("$"), Some("$"));
+    assert_eq!(kind("12a"), None);
+    let kind = |s: &[u8]| bytes_regex_switch!(s, Fi,
+        "ab+" => 1,
+        "[ab]+" => 2,
+    );
+    assert_eq!(kind(b"ABB"), Some(1));
+    assert_eq!(kind(b"ba"), Some(2));
+    assert_eq!(kind(b"bac"), None);
+}
+
+#[test]
+fn test_full_and_longest() {
+    let r: &AutomataRegex = regex!("a|ab|abc"LF);
+    assert!(r.is_match("abc"));
+    assert!(!r.is_match("abcd"));
+}
@@ -0,0 +1,71 @@
+use {
+    lazy_regex::*,
+};
+
+regex_grammar! {
+    octet = r"25[0-5]|2[0-4]\d|1?\d?\d";
+    pub ipv4 = r"(?&octet)(?:\.(?&octet)){3}";
+    pub(crate) port = r"\d{1,5}";
+    hostname = r"[a-z0-9](?:[a-z0-9-]*[a-z0-9])?(?:\.[a-z0-9](?:[a-z0-9-]*[a-z0-9])?)*"i;
+    pub endpoint = r"^(?:(?&ipv4)|(?&hostname)):(?&port)$";
+}
+
+#[test]
+fn test_grammar_expansion() {
+    assert_eq!(OCTET_PATTERN, r"25[0-5]|2[0-4]\d|1?\d?\d");
+    assert_eq!(
+        IPV4_PATTERN,
+        r"(?-imsxU:25[0-5]|2[0-4]\d|1?\d?\d)(?:\.(?-imsxU:25[0-5]|2[0-4]\d|1?\d?\d)){3}",
+    );
+    // the flags of a rule are kept when it's referenced
+    assert!(ENDPOINT_PATTERN.contains("(?i-msxU:[a-z0-9]"));
+}
+
+#[test]
+fn test_grammar_flags_dont_leak() {
+    // the flags of the referencing rule don't apply to the referenced one
+    regex_grammar! {
+        spaced = r"a b";
+        outer = r"^(?&spaced)c$"x;
+        lower = r"abc";
+        upper = r"^(?&lower)$"i;
+    }
+    assert!(OUTER.is_match("a bc"));
+    assert!(!OUTER.is_match("abc"));
+    assert!(UPPER.is_match("abc"));
+    assert!(!UPPER.is_match("ABC"));
+}
+
+#[test]
+fn test_grammar_statics() {
+    assert!(OCTET.is_match("255"));
+    assert!(IPV4.is_match("10.0.0.1"));
+    assert!(PORT.is_match("8080"));
+    assert!(ENDPOINT.is_match("192.168.1.12:80"));
+    assert!(ENDPOINT.is_match("Example.COM:443"));
+    assert!(!ENDPOINT.is_match("example.com"));
+    assert!(!ENDPOINT.is_match("-example.com:443"));
+}
+
+#[test]
+#[cfg(not(feature = "lite"))]
+fn test_grammar_in_function() {
+    regex_grammar! {
+        word = r"\w+";
+        pair = r"(?&word)=(?&word)"B;
+    }
+    let pair: &BytesRegex = &PAIR;
+    assert!(pair.is_match(b"a=b"));
+    assert!(!WORD.is_match("-"));
+}
+
+#[test]
+fn test_grammar_named_groups() {
+    // a rule with a named group can be referenced once in a pattern
+    regex_grammar! {
+        year = r"(?<year>\d{4})";
+        date = r"(?&year)-\d{2}-\d{2}";
+    }
+    let caps = DATE.captures("on 2026-10-18").unwrap();
+    assert_eq!(&caps["year"], "2026");
+}
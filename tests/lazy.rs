@@ -0,0 +1,17 @@
+use {
+    lazy_regex::*,
+    std::thread,
+};
+
+pub static WORD: Lazy<Regex> = lazy_regex!(r"\w+");
+
+#[test]
+fn test_concurrent_first_use() {
+    let handles: Vec<_> = (0..8)
+        .map(|i| thread::spawn(move || WORD.find(&format!(" w{i} ")).map(|m| m.len())))
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), Some(2));
+    }
+    assert!(Lazy::force(&WORD).is_match("a"));
+}
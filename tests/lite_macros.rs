@@ -0,0 +1,41 @@
+#![cfg(feature = "lite-macros")]
+
+use {
+    lazy_regex::*,
+};
+
+pub static WORDS: Lazy<LiteRegex> = lite_lazy_regex!(r"\w+"i, crlf);
+
+#[test]
+fn test_lite_regex() {
+    let r: &LiteRegex = lite_regex!("^ab+$"i);
+    assert!(r.is_match("aBb"));
+    assert_eq!(WORDS.find_iter("a bc").count(), 2);
+    // the other macros keep the standard engine
+    let r: &Regex = regex!("^ab+$");
+    assert!(r.is_match("abb"));
+}
+
+#[test]
+fn test_lite_macros() {
+    assert!(lite_regex_is_match!("[ab]+", "car"));
+    assert_eq!(lite_regex_find!(r"\bf\w+\b", "The fox jumps."), Some("fox"));
+    let (_, name, version) = lite_regex_captures!(
+        r"(\w+)-([0-9.]+)",
+        "This is lazy_regex-2.0!",
+    ).unwrap();
+    assert_eq!((name, version), ("lazy_regex", "2.0"));
+    let version = lite_regex_captures!(r"-([0-9.]+)", "lazy_regex-2.0" => (_, v) => v);
+    assert_eq!(version, Some("2.0"));
+    let keys: Vec<_> = lite_regex_captures_iter!(r"(\w)=\d", "a=1 b=2")
+        .map(|caps| caps[1].to_string())
+        .collect();
+    assert_eq!(keys, ["a", "b"]);
+}
+
+#[test]
+fn test_lite_replace() {
+    let text = lite_regex_replace!("f(u*)"i, "Fuu fuuu", |_, suffix: &str| format!("F{}", suffix.len()));
+    assert_eq!(text, "F2 fuuu");
+    assert_eq!(lite_regex_replace_all!(r"\d+", "a1 b22", "#"), "a# b#");
+}
@@ -0,0 +1,67 @@
+use {
+    lazy_regex::*,
+    std::borrow::Cow,
+};
+
+// Those patterns are searched without regex when the `perf-literal`
+// feature is enabled: the results must be the same as with a regex.
+// The texts aren't literals, which would be searched at compile time.
+
+#[test]
+fn test_literal_is_match() {
+    let (foo, cap_foo, dot, bar) = ("a foo", "a FoO", "a.b", String::from("some bar"));
+    assert!(regex_is_match!("foo", foo));
+    assert!(!regex_is_match!("foo", cap_foo));
+    assert!(regex_is_match!("foo"i, cap_foo));
+    assert!(regex_is_match!("foo|bar", &bar));
+    assert!(regex_is_match!("foo|bar"i, &bar));
+    assert!(regex_is_match!(r"a\.b", dot));
+    assert!(!regex_is_match!(r"a\.b", foo));
+    let summer = "un été";
+    assert!(regex_is_match!("été", summer));
+}
+
+#[test]
+fn test_literal_find() {
+    let text = "lazy-regex";
+    assert_eq!(regex_find!("lazy", text), Some("lazy"));
+    assert_eq!(regex_find!("REGEX"i, text), Some("regex"));
+    assert_eq!(regex_find!("q", text), None);
+    // leftmost-first, as with a regex
+    let text = "samwise";
+    assert_eq!(regex_find!("sam|samwise", text), Some("sam"));
+    assert_eq!(regex_find!("samwise|sam", text), Some("samwise"));
+    assert_eq!(regex_find!("wise|sam", text), Some("sam"));
+    // a group doesn't change the match
+    assert_eq!(regex_find!("(wise)", text), Some("wise"));
+}
+
+#[test]
+fn test_literal_remove_all() {
+    let (dashes, letters, a, spaces) = ("a--b--c", "xAyBz", "aaa", " abc ");
+    assert_eq!(regex_remove_all!("--", dashes), "abc");
+    assert_eq!(regex_remove_all!("a|b"i, letters), "xyz");
+    assert_eq!(regex_remove_all!("aa", a), "a");
+    assert!(matches!(regex_remove_all!(" ", spaces), Cow::Borrowed("abc")));
+}
+
+#[test]
+#[cfg(not(feature = "lite"))]
+fn test_literal_case_folding() {
+    // `k` also matches the Kelvin sign with Unicode, so it's not searched as an ASCII literal
+    let (kelvin, long_s) = ("\u{212A}", "\u{17F}");
+    assert!(regex_is_match!("k"i, kelvin));
+    assert!(regex_is_match!("s"i, long_s));
+    assert!(!regex_is_match!("(?-u)k"i, kelvin));
+}
+
+#[test]
+#[cfg(not(feature = "lite"))]
+fn test_literal_bytes() {
+    let (foo, cap_foo, jpeg) = (b"a foo", b"a FOO", b"..\xFF\xD8");
+    assert!(bytes_regex_is_match!("foo", foo));
+    assert_eq!(bytes_regex_find!("foo"i, cap_foo), Some(&b"FOO"[..]));
+    assert_eq!(regex_find!(b"\xFF\xD8"B, jpeg), Some(&b"\xFF\xD8"[..]));
+    let dashes = b"a-b-c";
+    assert_eq!(bytes_regex_remove_all!("-", dashes), &b"abc"[..]);
+}
@@ -0,0 +1,69 @@
+#![cfg(not(feature = "lite"))]
+
+use {
+    lazy_regex::*,
+};
+
+pub static KEYWORD: Lazy<AutomataRegex> = lazy_regex!(r"\b(?:in|int|integer)"L);
+
+#[test]
+fn test_longest_find() {
+    // with the default leftmost-first semantics, the first alternative wins
+    assert_eq!(regex_find!("sam|samwise", "samwise"), Some("sam"));
+    // with the L flag, the longest one wins
+    assert_eq!(regex_find!("sam|samwise"L, "samwise"), Some("samwise"));
+    // but the leftmost match is still preferred
+    assert_eq!(regex_find!("sam|samwise"L, "sam samwise"), Some("sam"));
+    assert_eq!(regex_find!("b|ab"L, "xab"), Some("ab"));
+    assert_eq!(regex_find!("SAM|samwise"iL, "SAMWISE"), Some("SAMWISE"));
+    assert_eq!(regex_find!("a+?"L, "aaa"), Some("aaa"));
+    assert_eq!(regex_find!("z"L, "aaa"), None);
+    assert!(regex_is_match!("sam|samwise"L, "samwise"));
+    assert_eq!(KEYWORD.find("int x").unwrap().as_str(), "int");
+    assert_eq!(KEYWORD.find("integer x").unwrap().as_str(), "integer");
+}
+
+#[test]
+fn test_longest_find_iter() {
+    let words: Vec<&str> = regex!("a|ab|abc"L)
+        .find_iter("abc ab a")
+        .map(|m| m.as_str())
+        .collect();
+    assert_eq!(words, vec!["abc", "ab", "a"]);
+}
+
+#[test]
+fn test_longest_captures() {
+    let (whole, op) = regex_captures!(r"x(=|==|===)"L, "x=== y").unwrap();
+    assert_eq!(whole, "x===");
+    assert_eq!(op, "===");
+    let (_, a, b) = regex_captures!(r"(\d+)(?:\.(\d+))?"L, "v3 then 4.2").unwrap();
+    assert_eq!(a, "3");
+    assert_eq!(b, "");
+    assert_eq!(regex_captures!("(a)|(b)"L, "c"), None);
+}
+
+#[test]
+fn test_longest_switch() {
+    fn token(s: &str) -> Option<&'static str> {
+        regex_switch!(s,
+            r"^(?:if|ifdef|ifndef)"L => "directive",
+            r"^\w+" => "identifier",
+        )
+    }
+    assert_eq!(token("ifdef X"), Some("directive"));
+    assert_eq!(token("foo"), Some("identifier"));
+    let kind = regex_switch!("ifndef",
+        "^(?<word>if|ifndef)"L => word.len(),
+    );
+    assert_eq!(kind, Some(6));
+}
+
+#[test]
+fn test_bytes_longest() {
+    assert_eq!(bytes_regex_find!("a|ab"L, b"ab"), Some(&b"ab"[..]));
+    let (_, op) = bytes_regex_captures!("(<|<=)"L, b"a <= b").unwrap();
+    assert_eq!(op, b"<=");
+    let r: &BytesAutomataRegex = regex!("(?-u:\\xFF)|(?-u:\\xFF)+"LB);
+    assert_eq!(r.find(b"a\xFF\xFF").unwrap().range(), 1..3);
+}
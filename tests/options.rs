@@ -0,0 +1,47 @@
+#![cfg(not(feature = "lite"))]
+
+use {
+    lazy_regex::*,
+};
+
+pub static CRLF_LINES: Lazy<Regex> = lazy_regex!("(?m)^a$", crlf);
+
+#[test]
+fn test_bool_options() {
+    assert!(CRLF_LINES.is_match("b\r\na\r\n"));
+    assert!(!regex!("(?m)^a$").is_match("b\r\na\r\n"));
+    assert!(regex!(r"\d", unicode = false).is_match("4"));
+    assert!(!regex!(r"\d", unicode = false).is_match("٤"));
+    assert!(regex!(r"\d").is_match("٤"));
+    assert!(regex!(r"\141", octal).is_match("a"));
+    assert!(regex!("AB", case_insensitive, ignore_whitespace = false).is_match("ab"));
+    assert!(regex!("a+$", multi_line,).is_match("aa\nb"));
+}
+
+#[test]
+fn test_int_options() {
+    let r = regex!(r"\w+", size_limit = 1 << 20, dfa_size_limit = 2 * (1 << 20));
+    assert!(r.is_match("word"));
+    assert!(regex!("(((a)))", nest_limit = 10).is_match("a"));
+    let r = regex!("(?m)^b$", line_terminator = b'\0');
+    assert!(r.is_match("a\0b\0c"));
+}
+
+#[test]
+fn test_bytes_options() {
+    let r = bytes_regex!(r"\xFF+", unicode = false);
+    assert!(r.is_match(b"a\xFF\xFF"));
+    let r: &BytesRegex = regex!(r"(?m)^x$"B, crlf);
+    assert!(r.is_match(b"x\r\n"));
+    pub static R: Lazy<BytesRegex> = bytes_lazy_regex!(r".", unicode = false, dot_matches_new_line);
+    assert!(R.is_match(b"\n"));
+}
+
+#[test]
+fn test_options_with_automata() {
+    let r: &AutomataRegex = regex!("(?m)^a|ab$"L, crlf);
+    assert_eq!(r.find("x\r\nab\r\n").map(|m| m.as_str()), Some("ab"));
+    assert!(regex_is_match!(r"\d+"F, "42"));
+    let r = regex!(r"\d+"F, unicode = false);
+    assert!(!r.is_match("٤٢"));
+}
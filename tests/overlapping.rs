@@ -0,0 +1,67 @@
+#![cfg(not(feature = "lite"))]
+
+use {
+    lazy_regex::*,
+};
+
+#[test]
+fn test_regex_find_overlapping() {
+    let matches: Vec<(usize, &str)> = regex_find_overlapping!(r"\d{3}", "12345")
+        .map(|m| (m.start(), m.as_str()))
+        .collect();
+    assert_eq!(matches, vec![(0, "123"), (1, "234"), (2, "345")]);
+
+    // find_iter would only give "aba" once
+    assert_eq!(regex_find_overlapping!("aba"i, "abAbA").count(), 2);
+
+    // searches start on char boundaries
+    let matches: Vec<&str> = regex_find_overlapping!("[a-zéà]+é", "déjà été")
+        .map(|m| m.as_str())
+        .collect();
+    assert_eq!(matches, vec!["dé", "été", "té"]);
+
+    assert!(regex_find_overlapping!("z", "abc").next().is_none());
+}
+
+#[test]
+fn test_regex_find_overlapping_same_start() {
+    // all the matches starting at a position are given, from the shortest
+    let matches: Vec<&str> = regex_find_overlapping!("a|ab", "ab")
+        .map(|m| m.as_str())
+        .collect();
+    assert_eq!(matches, vec!["a", "ab"]);
+    let matches: Vec<(usize, &str)> = regex_find_overlapping!("a+", "aaa")
+        .map(|m| (m.start(), m.as_str()))
+        .collect();
+    assert_eq!(matches, vec![(0, "a"), (0, "aa"), (0, "aaa"), (1, "a"), (1, "aa"), (2, "a")]);
+    // the order of preference of the alternation doesn't matter
+    assert_eq!(regex_find_overlapping!("ab|a", "ab").count(), 2);
+    // the flags are applied
+    assert_eq!(regex_find_overlapping!("^a"m, "b\na").count(), 1);
+}
+
+#[test]
+fn test_regex_find_overlapping_unicode_word_boundary() {
+    // the lazy DFA gives up on Unicode word boundaries in non ASCII text,
+    // the matches are then found with the other engine
+    let matches: Vec<&str> = regex_find_overlapping!(r"\w+\b", "été à")
+        .map(|m| m.as_str())
+        .collect();
+    assert_eq!(matches, vec!["été", "té", "é", "à"]);
+    let matches: Vec<&str> = regex_find_overlapping!(r"\bé\w*", "été")
+        .map(|m| m.as_str())
+        .collect();
+    assert_eq!(matches, vec!["é", "ét", "été"]);
+}
+
+#[test]
+fn test_bytes_regex_find_overlapping() {
+    let matches: Vec<&[u8]> = bytes_regex_find_overlapping!("(?-u:.)A", b"\xFFAA")
+        .map(|m| m.as_bytes())
+        .collect();
+    assert_eq!(matches, vec![&b"\xFFA"[..], &b"AA"[..]]);
+
+    // empty matches are found at every position, including the end
+    assert_eq!(regex_find_overlapping!("x*"B, b"ab").count(), 3);
+    assert_eq!(bytes_regex_find_overlapping!("a|ab", b"ab").count(), 2);
+}
@@ -0,0 +1,71 @@
+use {
+    lazy_regex::*,
+};
+
+regexes! {
+    /// An email address, roughly
+    pub EMAIL = r"^(?<user>[\w.+-]+)@(?<domain>[\w-]+(?:\.[\w-]+)+)$"i;
+    pub(crate) VERSION = r"(?<major>\d+)\.(?<minor>\d+)(?:\.(?<patch>\d+))?";
+    WORD = r"\w+";
+}
+
+mod sub {
+    lazy_regex::regexes! {
+        pub(super) KEY_VALUE = r"(?<key>\w+)=(?<value>\w*)";
+    }
+}
+
+#[test]
+fn test_regexes_statics() {
+    let _: &Regex = &EMAIL;
+    assert!(EMAIL.is_match("Someone@Example.com"));
+    assert!(!EMAIL.is_match("nobody"));
+    assert!(WORD.is_match("word"));
+    let caps = sub::KEY_VALUE.captures("a=b").unwrap();
+    assert_eq!(&caps[sub::KEY_VALUE::VALUE], "b");
+}
+
+#[test]
+fn test_regexes_group_indices() {
+    assert_eq!(EMAIL::USER, 1);
+    assert_eq!(EMAIL::DOMAIN, 2);
+    let caps = VERSION.captures("lazy-regex 3.6.0").unwrap();
+    assert_eq!(&caps[VERSION::MAJOR], "3");
+    assert_eq!(&caps[VERSION::MINOR], "6");
+    assert_eq!(caps.get(VERSION::PATCH).map(|m| m.as_str()), Some("0"));
+}
+
+#[test]
+#[cfg(not(feature = "lite"))]
+fn test_regexes_types() {
+    regexes! {
+        HEADER = r"^(?<magic>GIF8[79]a)"B;
+        LONGEST = r"(?<w>sam|samwise)"L;
+    }
+    let _: &BytesRegex = &HEADER;
+    let caps = HEADER.captures(b"GIF89a...").unwrap();
+    assert_eq!(&caps[HEADER::MAGIC], b"GIF89a");
+    let _: &AutomataRegex = &LONGEST;
+    let caps = LONGEST.captures("samwise").unwrap();
+    assert_eq!(caps.get(LONGEST::W).unwrap().as_str(), "samwise");
+}
+
+#[test]
+fn test_regexes_group_layout() {
+    const _: () = assert!(VERSION::CAPTURES_LEN == 4);
+    assert_eq!(
+        VERSION::GROUP_NAMES,
+        [None, Some("major"), Some("minor"), Some("patch")],
+    );
+    assert_eq!(WORD::CAPTURES_LEN, 1);
+}
+
+#[test]
+fn test_const_group_layout() {
+    const LEN: usize = regex_captures_len!(r"(\w+)-(?<version>[\d.]+)");
+    const NAMES: [Option<&str>; LEN] = regex_group_names!(r"(\w+)-(?<version>[\d.]+)");
+    const _: () = assert!(LEN == 3);
+    assert_eq!(NAMES, [None, None, Some("version")]);
+    assert_eq!(regex_captures_len!("a(?:b)"), 1);
+    assert_eq!(regex_group_names!("(?<x>a)", flags = "i"), [None, Some("x")]);
+}
@@ -0,0 +1,94 @@
+#![cfg(not(feature = "lite"))]
+
+use {
+    lazy_regex::*,
+};
+
+#[test]
+fn test_regex_rfind() {
+    assert_eq!(
+        regex_rfind!(r"\d+(\.\d+)*", "tools/1.2/lazy-regex-3.6.0.tar.gz"),
+        Some("3.6.0"),
+    );
+    assert_eq!(regex_rfind!(r"\d+", "no digit here"), None);
+    assert_eq!(regex_rfind!("ab+"i, "ab ABBB aB a"), Some("aB"));
+    assert_eq!(regex_rfind!(r"\b\w+\b", "déjà vu, déjà là"), Some("là"));
+
+    // when matches overlap, the backward search finds the one ending last,
+    // not the last one of find_iter
+    assert_eq!(regex_rfind!(r"\d{2}|\bz", "a123"), Some("23"));
+    assert_eq!(
+        regex!(r"\d{2}|\bz").find_iter("a123").last().map(|m| m.as_str()),
+        Some("12"),
+    );
+}
+
+#[test]
+fn test_regex_rfind_forward_fallback() {
+    // the reverse DFA gives up on the non ASCII chars because of the
+    // Unicode word boundaries, and the last match of find_iter is returned
+    assert_eq!(regex_rfind!(r"\d{2}|\bz", "é123"), Some("12"));
+    assert_eq!(regex_rfind!(r"\b\d{2}|\bé", "é 123"), Some("12"));
+    assert_eq!(regex_rfind!(r"\b\w+\b", "là, déjà vu"), Some("vu"));
+}
+
+#[test]
+fn test_regex_rfind_flags() {
+    assert_eq!(regex_rfind!("a.b"s, "a\nb axb"), Some("axb"));
+    assert_eq!(regex_rfind!("^a+$"m, "aa\naaa\nb"), Some("aaa"));
+    assert_eq!(regex_rfind!("sam|samwise"L, "samwise and sam or samwise"), Some("samwise"));
+    assert_eq!(regex_rfind!("a+"F, "aaa"), Some("aaa"));
+    assert_eq!(regex_rfind!("a+"F, "aab"), None);
+}
+
+#[test]
+fn test_bytes_regex_rfind() {
+    assert_eq!(
+        bytes_regex_rfind!(r"\b\w+\b", b"The fox jumps."),
+        Some(&b"jumps"[..]),
+    );
+    assert_eq!(
+        regex_rfind!(r"\d+"B, b"a1b22c333d"),
+        Some(&b"333"[..]),
+    );
+    assert_eq!(bytes_regex_rfind!(r"\d{2}|\bz", "é123".as_bytes()), Some(&b"12"[..]));
+    assert_eq!(bytes_regex_rfind!(r"(?-u)\d+", b"\xFF12\xFE345"), Some(&b"345"[..]));
+}
+
+#[test]
+fn test_regex_rcaptures() {
+    let (whole, hour, minute) = regex_rcaptures!(
+        r"(\d{2}):(\d{2})",
+        "started at 10:42, ended at 11:07",
+    ).unwrap();
+    assert_eq!(whole, "11:07");
+    assert_eq!(hour, "11");
+    assert_eq!(minute, "07");
+
+    // optional groups with no value are given as ""
+    let (_, name, version) = regex_rcaptures!(
+        r"(\w+)(?:-(\d+))?;",
+        "lazy-3; regex;",
+    ).unwrap();
+    assert_eq!(name, "regex");
+    assert_eq!(version, "");
+
+    assert_eq!(regex_rcaptures!("(a)(b)", "ba"), None);
+
+    let (whole, tens) = regex_rcaptures!(r"(\d)\d", "é1234").unwrap();
+    assert_eq!(whole, "34");
+    assert_eq!(tens, "3");
+    let (whole, tens) = regex_rcaptures!(r"(\d)\d", "é123").unwrap();
+    assert_eq!(whole, "23");
+    assert_eq!(tens, "2");
+}
+
+#[test]
+fn test_bytes_regex_rcaptures() {
+    let (_, key, value) = bytes_regex_rcaptures!(
+        r"(\w+)=(\w+)",
+        b"a=1 b=2 c=3",
+    ).unwrap();
+    assert_eq!(key, b"c");
+    assert_eq!(value, b"3");
+}
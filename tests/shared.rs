@@ -0,0 +1,71 @@
+use {
+    lazy_regex::*,
+};
+
+// The regexes built at different call sites with the same pattern and
+// options share their compiled program, and so the string of their pattern
+
+fn same_regex(a: &Regex, b: &Regex) -> bool {
+    std::ptr::eq(a.as_str(), b.as_str())
+}
+
+fn first_word() -> &'static Regex {
+    regex!(r"^\w+")
+}
+
+fn first_word_again() -> &'static Regex {
+    regex!(r"^\w+")
+}
+
+#[test]
+fn test_shared_regex() {
+    assert!(same_regex(first_word(), first_word_again()));
+    assert!(same_regex(first_word(), regex!(r"^\w+")));
+    assert_eq!(regex_find!(r"^\w+", "lazy regex".to_string().as_str()), Some("lazy"));
+    // different flags or options make different regexes
+    assert!(!same_regex(first_word(), regex!(r"^\w+"i)));
+    assert!(!same_regex(first_word(), regex!(r"^\w+", size_limit = 1 << 20)));
+    assert!(regex!(r"^\w+"i).is_match("Lazy"));
+}
+
+#[test]
+#[cfg(not(feature = "lite"))]
+fn test_shared_bytes_regex() {
+    let a: &BytesRegex = bytes_regex!("^ab+");
+    let b: &BytesRegex = bytes_regex!("^ab+");
+    assert!(std::ptr::eq(a.as_str(), b.as_str()));
+    // a regex and a bytes regex aren't shared, even with the same pattern
+    assert!(!std::ptr::eq(a.as_str(), regex!("^ab+").as_str()));
+    assert!(a.is_match(b"abb"));
+}
+
+#[test]
+fn test_shared_hash_collision() {
+    // regexes whose keys have the same hash aren't mixed up
+    let a: Regex = shared(42, "key a", || Regex::new("a+").unwrap());
+    let b: Regex = shared(42, "key b", || Regex::new("b+").unwrap());
+    assert_eq!(a.as_str(), "a+");
+    assert_eq!(b.as_str(), "b+");
+    let again: Regex = shared(42, "key a", || Regex::new("c+").unwrap());
+    assert!(std::ptr::eq(a.as_str(), again.as_str()));
+}
+
+#[test]
+fn test_shared_regex_key() {
+    // the order of the options doesn't matter
+    let a = regex!(r"^\d+", size_limit = 1 << 20, nest_limit = 50);
+    let b = regex!(r"^\d+", nest_limit = 50, size_limit = 1 << 20);
+    assert!(same_regex(a, b));
+    assert!(!same_regex(a, regex!(r"^\d+", nest_limit = 60, size_limit = 1 << 20)));
+}
+
+#[test]
+#[cfg(not(any(feature = "lite", feature = "keep-verbose-patterns")))]
+fn test_shared_verbose_regex() {
+    // verbose patterns differing only in their comments share their regex,
+    // as they're minimized (except with regex-lite)
+    let a = regex!(r#"^(\d+) # the number"#x);
+    let b = regex!(r#"^(\d+) # some digits"#x);
+    assert!(same_regex(a, b));
+    assert!(a.is_match("42"));
+}
@@ -0,0 +1,55 @@
+use {
+    lazy_regex::*,
+    std::{
+        borrow::Cow,
+        thread,
+        thread::LocalKey,
+    },
+};
+
+#[test]
+fn test_thread_local_regex() {
+    let r: &'static LocalKey<Regex> = regex!(r"^\d+$"T);
+    assert!(r.with(|r| r.is_match("123")));
+    let r: &'static LocalKey<Regex> = regex!("ab+"iT);
+    assert!(r.with(|r| r.is_match("ABB")));
+}
+
+#[test]
+#[cfg(not(feature = "lite"))]
+fn test_thread_local_bytes() {
+    let r = bytes_regex!("^ab+$"T);
+    assert!(r.with(|r| r.is_match(b"abb")));
+    assert!(bytes_regex_is_match!("[ab]+"T, b"car"));
+    assert_eq!(regex_find!(r"\bf\w+\b"BT, b"The fox jumps."), Some(&b"fox"[..]));
+}
+
+#[test]
+fn test_thread_local_macros() {
+    assert!(regex_is_match!("[ab]+"T, "car"));
+    assert_eq!(regex_find!(r"\bf\w+\b"T, "The fox jumps."), Some("fox"));
+    let (_, name, version) = regex_captures!(r"(\w+)-([0-9.]+)"T, "lazy_regex-2.0").unwrap();
+    assert_eq!((name, version), ("lazy_regex", "2.0"));
+    let text = regex_replace_all!("f(u*)"iT, "Fuu fuuu", |_, suffix: &str| {
+        format!("F{}", suffix.len())
+    });
+    assert_eq!(text, "F2 F3");
+    assert_eq!(regex_replace!("a+"T, "baab", "c"), "bcb");
+    assert_eq!(regex_remove_all!(r"\s+"T, "a b  c"), Cow::<str>::Owned("abc".to_string()));
+}
+
+#[test]
+fn test_thread_local_threads() {
+    let handles: Vec<_> = (0..8)
+        .map(|i| {
+            thread::spawn(move || {
+                let text = format!("thread-{i}");
+                regex_captures!(r"^(\w+)-(\d+)$"T, &text)
+                    .map(|(_, _, n)| n.parse::<usize>().unwrap())
+            })
+        })
+        .collect();
+    for (i, handle) in handles.into_iter().enumerate() {
+        assert_eq!(handle.join().unwrap(), Some(i));
+    }
+}
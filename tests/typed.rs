@@ -0,0 +1,60 @@
+use {
+    lazy_regex::*,
+};
+
+pub static KEY_VALUE: Lazy<TypedRegex<2>> = lazy_regex!(r"(\w+)=(\w*)", typed);
+
+fn parse_version<'h>(r: &TypedRegex<3>, text: &'h str) -> Option<[&'h str; 3]> {
+    r.captures(text)
+}
+
+#[test]
+fn test_typed_captures() {
+    let r = regex!(r"(\d+)\.(\d+)\.(\d+)", typed);
+    assert_eq!(parse_version(r, "lazy-regex 3.1.0"), Some(["3", "1", "0"]));
+    assert_eq!(parse_version(r, "lazy-regex"), None);
+    let [key, value] = KEY_VALUE.captures("a=").unwrap();
+    assert_eq!((key, value), ("a", ""));
+    // optional groups without value are empty
+    let r: &TypedRegex<2> = regex!(r"(a)|(b)", typed);
+    assert_eq!(r.captures("b"), Some(["", "b"]));
+}
+
+#[test]
+fn test_typed_captures_iter() {
+    let pairs: Vec<_> = KEY_VALUE.captures_iter("a=1, b=2").collect();
+    assert_eq!(pairs, vec![["a", "1"], ["b", "2"]]);
+    let r: &TypedRegex<0> = regex!(r"\w+"i, typed);
+    assert_eq!(r.captures_iter("a b").count(), 2);
+    // the wrapped regex is still available
+    assert_eq!(KEY_VALUE.find("x=y").unwrap().as_str(), "x=y");
+}
+
+#[test]
+fn test_typed_try_from() {
+    assert!(TypedRegex::<1>::try_from(Regex::new("(a)").unwrap()).is_ok());
+    let r = TypedRegex::<2>::try_from(Regex::new("(a)").unwrap()).unwrap_err();
+    assert_eq!(r.as_str(), "(a)");
+}
+
+#[cfg(not(feature = "lite"))]
+#[test]
+fn test_typed_bytes() {
+    let r: &TypedBytesRegex<2> = bytes_regex!(r"(\w+)=(\w*)", typed);
+    assert_eq!(r.captures(b"k=v"), Some([&b"k"[..], &b"v"[..]]));
+    let r: &TypedBytesRegex<1> = regex!(r"(\d)"B, typed);
+    assert_eq!(r.captures_iter(b"1 2").count(), 2);
+}
+
+fn captures_len<R: CapturesLen>(_: &R) -> usize {
+    R::CAPTURES_LEN
+}
+
+#[test]
+fn test_typed_captures_len() {
+    const _: () = assert!(<Lazy<TypedRegex<2>>>::CAPTURES_LEN == 3);
+    assert_eq!(captures_len(&KEY_VALUE), KEY_VALUE.captures_len());
+    assert_eq!(captures_len(regex!(r"\w+", typed)), 1);
+    #[cfg(not(feature = "lite"))]
+    assert_eq!(captures_len(regex!(r"(\d)(\d)"B, typed)), 3);
+}
@@ -0,0 +1,61 @@
+#![cfg(not(feature = "lite"))]
+
+use {
+    lazy_regex::*,
+};
+
+// The patterns of verbose regexes are stripped of their whitespace and
+// comments, and the x flag is removed when it's not needed anymore
+
+#[test]
+#[cfg(not(feature = "keep-verbose-patterns"))]
+fn test_verbose_pattern() {
+    let r = regex!(r#"
+        (?P<name>\w+)        # the name
+        -
+        (?P<version>[0-9.]+) # the version
+    "#x);
+    assert_eq!(r.as_str(), r"(?P<name>\w+)-(?P<version>[0-9.]+)");
+    let (_, name, version) = regex_captures!(r#"
+        (?P<name>\w+)        # the name
+        -
+        (?P<version>[0-9.]+) # the version
+    "#x, "This is lazy_regex-2.2!").unwrap();
+    assert_eq!((name, version), ("lazy_regex", "2.2"));
+    // escaped spaces and hashes still match
+    let r = regex!(r"a \  b \# # a, a space, b and a hash"x);
+    assert_eq!(r.as_str(), r"a\ b\#");
+    assert!(r.is_match("a b#"));
+    assert!(!r.is_match("ab#"));
+    // the inline flag is kept
+    let r = regex!(r"(?x)
+        a + # some a
+        [ b c ]
+    ");
+    assert_eq!(r.as_str(), "(?x)a+[bc]");
+    assert!(r.is_match("aac"));
+    // patterns without the verbose mode are unchanged
+    assert_eq!(regex!(r"(?i) a [ b ]").as_str(), r"(?i) a [ b ]");
+}
+
+#[test]
+#[cfg(not(feature = "keep-verbose-patterns"))]
+fn test_verbose_bytes_pattern() {
+    let r = bytes_regex!(r"
+        \x00 PNG   # the signature
+    "x);
+    assert_eq!(r.as_str(), r"\x00PNG");
+    assert!(r.is_match(b"\x00PNG"));
+}
+
+#[test]
+#[cfg(feature = "keep-verbose-patterns")]
+fn test_keep_verbose_pattern() {
+    let r = regex!("a b # a and b"x);
+    if cfg!(debug_assertions) {
+        assert_eq!(r.as_str(), "a b # a and b");
+    } else {
+        assert_eq!(r.as_str(), "ab");
+    }
+    assert!(r.is_match("ab"));
+}